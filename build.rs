@@ -0,0 +1,12 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // The generated server is only used by the mock disperser, so it is
+    // compiled only where the mock is.
+    tonic_build::configure()
+        .build_server(true)
+        .server_mod_attribute(".", r#"#[cfg(any(test, feature = "mock"))]"#)
+        .compile(
+            &["eigenda/api/proto/disperser/disperser.proto"],
+            &["eigenda/api/proto"],
+        )?;
+    Ok(())
+}
@@ -0,0 +1,403 @@
+use crate::batch::BatchHeaderHash;
+use crate::blob::EncodedBlob;
+use crate::chunked::{ChunkedOptions, Manifest, ManifestChunk, MANIFEST_TYPE_TAG};
+use crate::compression::Compression;
+use crate::encoding::BlobEncoding;
+use crate::encryption::{EncryptionKey, KeyProvider};
+use crate::error::EigenDaError;
+use crate::methods::EigenDaGrpcMethod;
+use crate::packer::{BlobPacker, MessageLocator};
+use crate::payload::{DisperseOptions, EigenDaBlobPayload};
+use crate::reference::BlobReference;
+use crate::response::BlobResponse;
+use crate::result::Result;
+use crate::retry::RetryPolicy;
+use crate::signer::Signer;
+use crate::state::{ConfirmationWait, ConfirmedBlob};
+use crate::status::BlobStatus;
+use crate::timeout::Timeouts;
+use crate::transport::AsyncDisperserTransport;
+use crate::typed::Format;
+use derive_builder::Builder;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Non-blocking client for the EigenDA disperser, the async counterpart of
+/// [`EigenDaGrpcClient`](crate::client::EigenDaGrpcClient).
+///
+/// The default client uses [`TonicTransport`](crate::native::TonicTransport),
+/// or `GrpcurlTransport` when the `grpcurl` feature is enabled.
+#[derive(Builder, Clone, Debug)]
+pub struct AsyncEigenDaClient {
+    #[builder(setter(custom))]
+    transport: Arc<dyn AsyncDisperserTransport>,
+    /// When set, blobs are dispersed with `DisperseBlobAuthenticated`.
+    #[builder(default, setter(custom))]
+    signer: Option<Arc<dyn Signer>>,
+    /// When set, failed requests are retried as the policy allows.
+    #[builder(default, setter(custom))]
+    retry_policy: Option<RetryPolicy>,
+    #[builder(default, setter(custom))]
+    timeouts: Timeouts,
+    /// How `disperse_data` and `disperse_typed` compress data.
+    #[builder(default)]
+    compression: Compression,
+    /// When set, `disperse_data` and `disperse_typed` encrypt data with
+    /// this key.
+    #[builder(default, setter(custom))]
+    encryption_key: Option<EncryptionKey>,
+    /// Resolves the keys of encrypted blobs in `retrieve_data` and
+    /// `retrieve_typed`.
+    #[builder(default, setter(custom))]
+    key_provider: Option<Arc<dyn KeyProvider>>,
+}
+
+impl Default for AsyncEigenDaClient {
+    fn default() -> Self {
+        #[cfg(feature = "grpcurl")]
+        let transport = crate::grpcurl::GrpcurlTransport::default();
+        #[cfg(not(feature = "grpcurl"))]
+        let transport = crate::native::TonicTransport::default();
+
+        AsyncEigenDaClientBuilder::default()
+            .transport(transport)
+            .build()
+            .expect("failed to build async eigenda client.")
+    }
+}
+
+impl AsyncEigenDaClientBuilder {
+    pub fn transport<T: AsyncDisperserTransport + 'static>(&mut self, transport: T) -> &mut Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn signer<S: Signer + 'static>(&mut self, signer: S) -> &mut Self {
+        self.signer = Some(Some(Arc::new(signer)));
+        self
+    }
+
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = Some(Some(retry_policy));
+        self
+    }
+
+    pub fn encryption_key(&mut self, encryption_key: EncryptionKey) -> &mut Self {
+        self.encryption_key = Some(Some(encryption_key));
+        self
+    }
+
+    pub fn key_provider<P: KeyProvider + 'static>(&mut self, key_provider: P) -> &mut Self {
+        self.key_provider = Some(Some(Arc::new(key_provider)));
+        self
+    }
+
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeouts = Some(self.timeouts.unwrap_or_default().with_connect(timeout));
+        self
+    }
+
+    pub fn rpc_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeouts = Some(self.timeouts.unwrap_or_default().with_rpc(timeout));
+        self
+    }
+}
+
+impl AsyncEigenDaClient {
+    pub fn transport(&self) -> &Arc<dyn AsyncDisperserTransport> {
+        &self.transport
+    }
+
+    pub fn signer(&self) -> Option<&Arc<dyn Signer>> {
+        self.signer.as_ref()
+    }
+
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub fn encryption_key(&self) -> Option<&EncryptionKey> {
+        self.encryption_key.as_ref()
+    }
+
+    pub fn key_provider(&self) -> Option<&Arc<dyn KeyProvider>> {
+        self.key_provider.as_ref()
+    }
+
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    /// A client sharing this one's transport, whose requests use the
+    /// timeouts set in `timeouts` instead of its own.
+    pub fn with_timeouts(&self, timeouts: Timeouts) -> Self {
+        let mut client = self.clone();
+        client.timeouts = self.timeouts.merge(&timeouts);
+        client
+    }
+
+    async fn send<T, F, Fut>(&self, method: EigenDaGrpcMethod, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        match &self.retry_policy {
+            Some(retry_policy) => retry_policy.run_async(method, request).await,
+            None => request().await,
+        }
+    }
+
+    fn encoding(&self) -> BlobEncoding<'_> {
+        BlobEncoding {
+            compression: self.compression,
+            encryption_key: self.encryption_key.as_ref(),
+            key_provider: self.key_provider.as_deref(),
+        }
+    }
+
+    pub async fn disperse_blob(&self, encoded_data: String) -> Result<BlobResponse> {
+        self.disperse_blob_with_options(encoded_data, DisperseOptions::default())
+            .await
+    }
+
+    /// Disperse a blob with custom quorums or an account id, which are
+    /// validated before the request is sent.
+    pub async fn disperse_blob_with_options(
+        &self,
+        encoded_data: String,
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let payload = EigenDaBlobPayload::validated(encoded_data, options)?;
+        match &self.signer {
+            Some(signer) => {
+                self.send(EigenDaGrpcMethod::DisperseBlobAuthenticated, || {
+                    self.transport.disperse_blob_authenticated(
+                        &payload,
+                        signer.as_ref(),
+                        &self.timeouts,
+                    )
+                })
+                .await
+            }
+            None => {
+                self.send(EigenDaGrpcMethod::DisperseBlob, || {
+                    self.transport.disperse_blob(&payload, &self.timeouts)
+                })
+                .await
+            }
+        }
+    }
+
+    pub async fn get_blob_status(&self, request_id: &str) -> Result<BlobStatus> {
+        self.send(EigenDaGrpcMethod::GetBlobStatus, || {
+            self.transport.get_blob_status(request_id, &self.timeouts)
+        })
+        .await
+    }
+
+    pub async fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+    ) -> Result<EncodedBlob> {
+        self.send(EigenDaGrpcMethod::RetrieveBlob, || {
+            self.transport
+                .retrieve_blob(batch_header_hash, blob_index, &self.timeouts)
+        })
+        .await
+    }
+
+    /// Disperse arbitrary bytes, wrapped in an [`envelope`](crate::envelope)
+    /// so every field element of the blob is valid and the data can be
+    /// retrieved exactly.
+    pub async fn disperse_data(&self, data: &[u8]) -> Result<BlobResponse> {
+        self.disperse_data_with_options(data, DisperseOptions::default())
+            .await
+    }
+
+    pub async fn disperse_data_with_options(
+        &self,
+        data: &[u8],
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let blob = self.encoding().encode(data, Format::Raw)?;
+        self.disperse_blob_with_options(blob, options).await
+    }
+
+    /// Retrieve bytes dispersed with [`AsyncEigenDaClient::disperse_data`].
+    /// Fails if the blob is not an envelope this crate can read; blobs
+    /// dispersed without one can be read with [`AsyncEigenDaClient::retrieve_blob`]
+    /// and [`DecodedBlob::from_raw`](crate::blob::DecodedBlob::from_raw).
+    pub async fn retrieve_data(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+    ) -> Result<Vec<u8>> {
+        let blob = self.retrieve_blob(batch_header_hash, blob_index).await?;
+        Ok(self.encoding().decode(blob)?.data())
+    }
+
+    /// Disperse `value` serialized in `format`, tagged with `type_tag` so
+    /// readers can check what they decode.
+    pub async fn disperse_typed<T: Serialize>(
+        &self,
+        value: &T,
+        format: Format,
+        type_tag: &str,
+    ) -> Result<BlobResponse> {
+        self.disperse_typed_with_options(value, format, type_tag, DisperseOptions::default())
+            .await
+    }
+
+    pub async fn disperse_typed_with_options<T: Serialize>(
+        &self,
+        value: &T,
+        format: Format,
+        type_tag: &str,
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let blob = self.encoding().encode_typed(value, format, type_tag)?;
+        self.disperse_blob_with_options(blob, options).await
+    }
+
+    /// Retrieve a record dispersed with [`AsyncEigenDaClient::disperse_typed`],
+    /// failing with [`EigenDaError::TypeMismatch`](crate::error::EigenDaError::TypeMismatch)
+    /// if it was tagged with another type.
+    pub async fn retrieve_typed<T: DeserializeOwned>(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        type_tag: &str,
+    ) -> Result<T> {
+        let blob = self.retrieve_blob(batch_header_hash, blob_index).await?;
+        self.encoding().decode(blob)?.deserialize(type_tag)
+    }
+
+    /// Poll the status of `request_id` every `poll_interval` until the blob
+    /// is confirmed, failing if it fails or `timeout` passes first. A status
+    /// that is neither pending nor final, such as `UNKNOWN`, fails too
+    /// rather than being polled forever.
+    pub async fn wait_for_confirmation(
+        &self,
+        request_id: &str,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<ConfirmedBlob> {
+        let wait = ConfirmationWait::new(request_id, timeout);
+        loop {
+            if let Some(blob) = wait.check(self.get_blob_status(request_id).await?)? {
+                return Ok(blob);
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    async fn confirm(
+        &self,
+        response: &BlobResponse,
+        options: &ChunkedOptions,
+    ) -> Result<BlobReference> {
+        let blob = self
+            .wait_for_confirmation(
+                &response.request_id(),
+                options.poll_interval(),
+                options.confirmation_timeout(),
+            )
+            .await?;
+        Ok(blob.reference())
+    }
+
+    /// Disperse `data` of any size as chunks followed by a
+    /// [`Manifest`](crate::chunked::Manifest) of them, waiting for every
+    /// blob to be confirmed. Returns the reference of the manifest, from
+    /// which [`AsyncEigenDaClient::retrieve_chunked`] reassembles the data.
+    pub async fn disperse_chunked(
+        &self,
+        data: &[u8],
+        options: &ChunkedOptions,
+    ) -> Result<BlobReference> {
+        options.validate()?;
+        let entries: Vec<ManifestChunk> = stream::iter(data.chunks(options.chunk_size()))
+            .map(|chunk| async move {
+                let response = self
+                    .disperse_data_with_options(chunk, options.disperse_options().clone())
+                    .await?;
+                let reference = self.confirm(&response, options).await?;
+                Ok::<_, EigenDaError>(ManifestChunk::new(reference, chunk))
+            })
+            .buffered(options.max_concurrency())
+            .try_collect()
+            .await?;
+
+        let blob = self.encoding().encode_manifest(entries)?;
+        let response = self
+            .disperse_blob_with_options(blob, options.disperse_options().clone())
+            .await?;
+        self.confirm(&response, options).await
+    }
+
+    /// Retrieve data dispersed with [`AsyncEigenDaClient::disperse_chunked`]
+    /// from the reference of its manifest, checking every chunk against the
+    /// hash the manifest lists.
+    pub async fn retrieve_chunked(
+        &self,
+        manifest: &BlobReference,
+        options: &ChunkedOptions,
+    ) -> Result<Vec<u8>> {
+        options.validate()?;
+        let manifest: Manifest = self
+            .retrieve_typed(
+                manifest.batch_header_hash(),
+                manifest.blob_index(),
+                MANIFEST_TYPE_TAG,
+            )
+            .await?;
+        let chunks: Vec<Vec<u8>> = stream::iter(manifest.chunks())
+            .map(|entry| {
+                let reference = entry.reference();
+                self.retrieve_data(reference.batch_header_hash(), reference.blob_index())
+            })
+            .buffered(options.max_concurrency())
+            .try_collect()
+            .await?;
+        manifest.assemble(chunks)
+    }
+
+    /// Disperse the messages collected by `packer` as one blob. Once it is
+    /// confirmed, [`BlobPacker::locators`] gives the locator of each
+    /// message.
+    pub async fn disperse_packed(&self, packer: &BlobPacker) -> Result<BlobResponse> {
+        self.disperse_packed_with_options(packer, DisperseOptions::default())
+            .await
+    }
+
+    pub async fn disperse_packed_with_options(
+        &self,
+        packer: &BlobPacker,
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let blob = self.encoding().encode_packed(packer)?;
+        self.disperse_blob_with_options(blob, options).await
+    }
+
+    /// Retrieve a single message dispersed with `disperse_packed`.
+    pub async fn retrieve_message(&self, locator: &MessageLocator) -> Result<Vec<u8>> {
+        let reference = locator.blob();
+        let blob = self
+            .retrieve_blob(reference.batch_header_hash(), reference.blob_index())
+            .await?;
+        Ok(self
+            .encoding()
+            .decode(blob)?
+            .message(locator.index())?
+            .to_vec())
+    }
+}
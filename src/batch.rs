@@ -1,26 +1,14 @@
-use crate::quorum::{BlobQuorumNumbers, BlobQuorumSignedPercentages};
+use crate::bytes::fixed_bytes;
+use crate::quorum::{self, BlobQuorumNumbers, BlobQuorumSignedPercentages};
+use crate::result::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct BatchHeaderHash(String);
-
-impl BatchHeaderHash {
-    pub fn new(value: String) -> Self {
-        Self(value)
-    }
-}
-
-impl ToString for BatchHeaderHash {
-    fn to_string(&self) -> String {
-        self.0.clone()
-    }
-}
-
-impl From<String> for BatchHeaderHash {
-    fn from(value: String) -> Self {
-        BatchHeaderHash::new(value)
-    }
-}
+fixed_bytes!(
+    /// The keccak256 hash of a batch header, which identifies the batch.
+    BatchHeaderHash,
+    32
+);
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -28,7 +16,8 @@ pub struct BatchHeader {
     batch_root: BlobBatchRoot,
     quorum_numbers: BlobQuorumNumbers,
     quorum_signed_percentages: BlobQuorumSignedPercentages,
-    reference_block_number: u128,
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    reference_block_number: u32,
 }
 
 impl BatchHeader {
@@ -44,16 +33,24 @@ impl BatchHeader {
         &self.quorum_signed_percentages
     }
 
-    pub fn reference_block_number(&self) -> u128 {
-        self.reference_block_number
+    /// The percentage of stake that signed in each quorum, by quorum id.
+    pub fn signed_percentages(&self) -> Result<BTreeMap<u8, u8>> {
+        quorum::signed_percentages_by_quorum(&self.quorum_numbers, &self.quorum_signed_percentages)
     }
-}
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct BlobBatchRoot(String);
+    /// The percentage of stake that signed in `quorum_id`, if the batch
+    /// was signed by it.
+    pub fn signed_percentage(&self, quorum_id: u8) -> Result<Option<u8>> {
+        Ok(self.signed_percentages()?.get(&quorum_id).copied())
+    }
 
-impl ToString for BlobBatchRoot {
-    fn to_string(&self) -> String {
-        self.0.clone()
+    pub fn reference_block_number(&self) -> u32 {
+        self.reference_block_number
     }
 }
+
+fixed_bytes!(
+    /// The root of the Merkle tree over the blob headers of a batch.
+    BlobBatchRoot,
+    32
+);
@@ -17,16 +17,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .subcommand(parse_blob())
         .get_matches();
 
-    match matches.subcommand() {
-        Some(("parse-blob", children)) => {
-            let json = children.get_one::<String>("json").expect("required");
-            let re = Regex::new(r"(\\n|\\t|\n\t|\s\s+)").unwrap();
-            let clean_json = re.replace_all(json, " ").to_string();
-            println!("\n{:?}", clean_json);
-            let blob_status: BlobStatus = BlobStatus::from_str(&clean_json)?;
-            println!("{:?}", blob_status);
-        }
-        _ => {}
+    if let Some(("parse-blob", children)) = matches.subcommand() {
+        let json = children.get_one::<String>("json").expect("required");
+        let re = Regex::new(r"(\\n|\\t|\n\t|\s\s+)").unwrap();
+        let clean_json = re.replace_all(json, " ").to_string();
+        println!("\n{:?}", clean_json);
+        let blob_status: BlobStatus = BlobStatus::from_str(&clean_json)?;
+        println!("{:?}", blob_status);
     }
     Ok(())
 }
@@ -1,13 +1,28 @@
+use crate::compression::DEFAULT_MAX_DECOMPRESSED_SIZE;
+use crate::encryption::KeyProvider;
+use crate::envelope;
+use crate::error::EigenDaError;
+use crate::packer;
+use crate::typed::{self, Format};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EncodedBlob {
     data: String,
 }
 
+impl FromStr for EncodedBlob {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 impl EncodedBlob {
-    pub fn from_str(value: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(value)
+    pub fn new(data: String) -> Self {
+        EncodedBlob { data }
     }
 
     pub fn data(&self) -> String {
@@ -18,24 +33,134 @@ impl EncodedBlob {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DecodedBlob {
     data: Vec<u8>,
+    #[serde(default)]
+    format: Format,
+    #[serde(default)]
+    type_tag: Option<String>,
 }
 
 impl DecodedBlob {
-    pub fn from_encoded(blob: EncodedBlob) -> Result<Self, base64::DecodeError> {
-        let decoded = base64::decode(&blob.data())?;
+    /// Decode a blob dispersed as an [`envelope`], yielding exactly the
+    /// bytes that were dispersed. Fails on envelopes of unknown versions and
+    /// on blobs without an envelope, which [`DecodedBlob::from_raw`] reads.
+    pub fn from_encoded(blob: EncodedBlob) -> crate::result::Result<Self> {
+        Self::from_encoded_with_limit(blob, DEFAULT_MAX_DECOMPRESSED_SIZE)
+    }
 
-        Ok(Self { data: decoded })
+    /// Decode a blob dispersed without an envelope, such as by
+    /// [`EigenDaGrpcClient::disperse_blob`](crate::client::EigenDaGrpcClient::disperse_blob)
+    /// or an older version of this crate. The bytes are returned as
+    /// retrieved, with the disperser's padding; data encoded with
+    /// [`codec::encode`](crate::codec::encode) still needs
+    /// [`codec::decode`](crate::codec::decode).
+    pub fn from_raw(blob: EncodedBlob) -> crate::result::Result<Self> {
+        Ok(Self {
+            data: base64::decode(blob.data())?,
+            format: Format::Raw,
+            type_tag: None,
+        })
     }
 
+    /// Like [`DecodedBlob::from_encoded`], failing if the blob decompresses
+    /// to more than `max_decompressed_size` bytes.
+    pub fn from_encoded_with_limit(
+        blob: EncodedBlob,
+        max_decompressed_size: usize,
+    ) -> crate::result::Result<Self> {
+        Self::from_encoded_with(blob, max_decompressed_size, None)
+    }
+
+    /// Decode a blob that may be encrypted, looking its key up in `keys`.
+    pub fn from_encrypted(
+        blob: EncodedBlob,
+        keys: &dyn KeyProvider,
+    ) -> crate::result::Result<Self> {
+        Self::from_encoded_with(blob, DEFAULT_MAX_DECOMPRESSED_SIZE, Some(keys))
+    }
+
+    /// Like [`DecodedBlob::from_encoded_with_limit`], decrypting encrypted
+    /// blobs with the keys of `key_provider`.
+    pub fn from_encoded_with(
+        blob: EncodedBlob,
+        max_decompressed_size: usize,
+        key_provider: Option<&dyn KeyProvider>,
+    ) -> crate::result::Result<Self> {
+        let decoded = base64::decode(blob.data())?;
+        let (header, data) = envelope::decode_with(&decoded, max_decompressed_size, key_provider)?;
+
+        let format = header.format();
+        if !format.is_typed() {
+            return Ok(Self {
+                data,
+                format,
+                type_tag: None,
+            });
+        }
+        let (type_tag, body) = typed::split_type_tag(&data)?;
+        Ok(Self {
+            data: body.to_vec(),
+            format,
+            type_tag: Some(type_tag),
+        })
+    }
+
+    /// The dispersed bytes, or the serialized record of a typed blob.
     pub fn data(&self) -> Vec<u8> {
         self.data.clone()
     }
 
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// The type tag a typed blob was dispersed with.
+    pub fn type_tag(&self) -> Option<&str> {
+        self.type_tag.as_deref()
+    }
+
+    /// Deserialize a typed blob, checking it was dispersed with `type_tag`.
+    pub fn deserialize<T: DeserializeOwned>(&self, type_tag: &str) -> crate::result::Result<T> {
+        if self.type_tag.as_deref() != Some(type_tag) {
+            return Err(EigenDaError::TypeMismatch {
+                expected: type_tag.to_string(),
+                found: self.type_tag.clone().unwrap_or_default(),
+            });
+        }
+        typed::deserialize(&self.data, self.format)
+    }
+
+    /// The number of messages in a packed blob.
+    pub fn message_count(&self) -> crate::result::Result<u32> {
+        self.check_packed()?;
+        packer::message_count(&self.data)
+    }
+
+    /// The message at `index` of a packed blob, read without parsing the
+    /// other messages.
+    pub fn message(&self, index: u32) -> crate::result::Result<&[u8]> {
+        self.check_packed()?;
+        packer::message(&self.data, index)
+    }
+
+    fn check_packed(&self) -> crate::result::Result<()> {
+        if self.format != Format::Packed {
+            return Err(EigenDaError::Codec(format!(
+                "blob is {:?}, not packed messages",
+                self.format
+            )));
+        }
+        Ok(())
+    }
+
     pub fn data_to_string(&self) -> Result<String, std::string::FromUtf8Error> {
-        todo!();
+        String::from_utf8(self.data.clone())
     }
 
     pub fn len(&self) -> usize {
         self.data.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
 }
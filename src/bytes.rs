@@ -0,0 +1,196 @@
+//! Byte strings from the disperser's replies, such as hashes and roots.
+//!
+//! They are kept decoded, serialized in the base64 form of the disperser's
+//! JSON and shown as `0x` prefixed hex. Comparisons run in constant time, so
+//! a hash can be checked against a secret or on-chain value safely.
+use crate::error::EigenDaError;
+use crate::result::Result;
+
+/// `0x` prefixed lowercase hex of `bytes`.
+pub fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Parse hex, with or without a `0x` prefix.
+pub fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(digits).map_err(|err| EigenDaError::Codec(format!("invalid hex: {}", err)))
+}
+
+/// Parse `0x` prefixed hex, or base64 otherwise. Base64 may itself start
+/// with `0x`, so a `0x` prefixed value that is not hex is read as base64.
+pub fn decode_hex_or_base64(value: &str) -> Result<Vec<u8>> {
+    if value.starts_with("0x") {
+        decode_hex(value).or_else(|err| base64::decode(value).map_err(|_| err))
+    } else {
+        Ok(base64::decode(value)?)
+    }
+}
+
+/// Parse `N` bytes of hex, with or without a `0x` prefix, or of base64,
+/// telling them apart by length.
+pub fn decode_fixed<const N: usize>(value: &str) -> Result<[u8; N]> {
+    let bytes = if value.len() == 2 * N + 2 || value.len() == 2 * N {
+        decode_hex(value)?
+    } else if value.len() == N.div_ceil(3) * 4 {
+        base64::decode(value)?
+    } else {
+        decode_hex_or_base64(value)?
+    };
+    to_array(&bytes)
+}
+
+/// Copy `bytes` into an array, failing if they are not `N` long.
+pub fn to_array<const N: usize>(bytes: &[u8]) -> Result<[u8; N]> {
+    bytes
+        .try_into()
+        .map_err(|_| EigenDaError::Codec(format!("expected {} bytes, found {}", N, bytes.len())))
+}
+
+/// Serde of byte strings as base64 strings, the form grpcurl and the proto
+/// JSON mapping use. Hex is not accepted: base64 may itself start with `0x`.
+pub mod base64_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        base64::decode(value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Define a newtype over `[u8; $len]` with hex and base64 conversions,
+/// base64 serde and constant-time equality.
+macro_rules! fixed_bytes {
+    ($(#[$meta:meta])* $name:ident, $len:expr) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        pub struct $name([u8; $len]);
+
+        impl $name {
+            /// Bytes in the value.
+            pub const LEN: usize = $len;
+
+            pub fn new(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            /// `0x` prefixed hex, as shown by `Display`.
+            pub fn to_hex(&self) -> String {
+                $crate::bytes::to_hex(&self.0)
+            }
+
+            /// The base64 form the disperser sends and expects.
+            pub fn to_base64(&self) -> String {
+                base64::encode(self.0)
+            }
+
+            /// Parse hex, with or without a `0x` prefix.
+            pub fn from_hex(value: &str) -> $crate::result::Result<Self> {
+                Self::try_from($crate::bytes::decode_hex(value)?.as_slice())
+            }
+
+            pub fn from_base64(value: &str) -> $crate::result::Result<Self> {
+                Self::try_from(base64::decode(value)?.as_slice())
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self([0; $len])
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = $crate::error::EigenDaError;
+
+            fn try_from(bytes: &[u8]) -> $crate::result::Result<Self> {
+                $crate::bytes::to_array(bytes).map(Self)
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        /// Parses hex, with or without a `0x` prefix, or base64, told
+        /// apart by length.
+        impl std::str::FromStr for $name {
+            type Err = $crate::error::EigenDaError;
+
+            fn from_str(value: &str) -> $crate::result::Result<Self> {
+                $crate::bytes::decode_fixed(value).map(Self)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.to_hex())
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.to_hex())
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..]).into()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $crate::bytes::base64_serde::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                let bytes = $crate::bytes::base64_serde::deserialize(deserializer)?;
+                Self::try_from(bytes.as_slice()).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+pub(crate) use fixed_bytes;
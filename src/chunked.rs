@@ -0,0 +1,240 @@
+//! Dispersal of data larger than a blob.
+//!
+//! The data is split into chunks, each dispersed as its own blob. Once every
+//! chunk is confirmed, a [`Manifest`] listing where each chunk was stored
+//! and the hash of its content is dispersed as a JSON record tagged
+//! [`MANIFEST_TYPE_TAG`]. The reference of the manifest blob is all a reader
+//! needs to reassemble the data.
+use crate::error::EigenDaError;
+use crate::payload::DisperseOptions;
+use crate::reference::BlobReference;
+use crate::result::Result;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The type tag of manifest records.
+pub const MANIFEST_TYPE_TAG: &str = "eigenda.manifest.v1";
+
+/// Bytes of data per chunk by default, leaving room under the disperser's
+/// 2 MiB blob limit for the envelope and field element encoding.
+pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// How many chunks are dispersed or retrieved at once by default.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+/// How long to wait for each blob to be confirmed by default, well past the
+/// disperser's usual batching and confirmation time.
+pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How chunked data is dispersed and retrieved.
+#[derive(Builder, Clone, Debug, PartialEq, Eq)]
+#[builder(default)]
+pub struct ChunkedOptions {
+    /// Bytes of data per chunk.
+    chunk_size: usize,
+    /// How many chunks are dispersed or retrieved at once.
+    max_concurrency: usize,
+    /// How often the status of a dispersed chunk is polled.
+    poll_interval: Duration,
+    /// How long to wait for each blob to be confirmed,
+    /// [`DEFAULT_CONFIRMATION_TIMEOUT`] by default, without limit after
+    /// `no_confirmation_timeout`.
+    #[builder(setter(strip_option))]
+    confirmation_timeout: Option<Duration>,
+    /// Sent with every chunk and the manifest.
+    disperse_options: DisperseOptions,
+}
+
+impl Default for ChunkedOptions {
+    fn default() -> Self {
+        ChunkedOptions {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            poll_interval: Duration::from_secs(5),
+            confirmation_timeout: Some(DEFAULT_CONFIRMATION_TIMEOUT),
+            disperse_options: DisperseOptions::default(),
+        }
+    }
+}
+
+impl ChunkedOptionsBuilder {
+    /// Wait for each blob to be confirmed without limit.
+    pub fn no_confirmation_timeout(&mut self) -> &mut Self {
+        self.confirmation_timeout = Some(None);
+        self
+    }
+}
+
+impl ChunkedOptions {
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    pub fn confirmation_timeout(&self) -> Option<Duration> {
+        self.confirmation_timeout
+    }
+
+    pub fn disperse_options(&self) -> &DisperseOptions {
+        &self.disperse_options
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.chunk_size == 0 {
+            return Err(EigenDaError::validation(
+                "chunk size must be greater than 0",
+            ));
+        }
+        if self.max_concurrency == 0 {
+            return Err(EigenDaError::validation(
+                "max concurrency must be greater than 0",
+            ));
+        }
+        self.disperse_options.validate()
+    }
+}
+
+/// The keccak256 hash of a chunk, `0x` prefixed hex.
+pub fn chunk_hash(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(Keccak256::digest(data)))
+}
+
+/// A dispersed chunk of chunked data.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestChunk {
+    reference: BlobReference,
+    length: u64,
+    hash: String,
+}
+
+impl ManifestChunk {
+    /// The entry of `data`, dispersed to `reference`.
+    pub fn new(reference: BlobReference, data: &[u8]) -> Self {
+        ManifestChunk {
+            reference,
+            length: data.len() as u64,
+            hash: chunk_hash(data),
+        }
+    }
+
+    pub fn reference(&self) -> &BlobReference {
+        &self.reference
+    }
+
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Check that `data`, retrieved for this chunk, is what was dispersed.
+    pub fn verify(&self, data: &[u8]) -> Result<()> {
+        if data.len() as u64 != self.length || chunk_hash(data) != self.hash {
+            return Err(EigenDaError::Codec(format!(
+                "chunk at batch {} index {} does not match its manifest hash",
+                self.reference.batch_header_hash(),
+                self.reference.blob_index()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The index of chunked data, listing its chunks in order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    length: u64,
+    chunks: Vec<ManifestChunk>,
+}
+
+impl Manifest {
+    pub fn new(chunks: Vec<ManifestChunk>) -> Self {
+        Manifest {
+            length: chunks.iter().map(ManifestChunk::length).sum(),
+            chunks,
+        }
+    }
+
+    /// The length of the whole data.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn chunks(&self) -> &[ManifestChunk] {
+        &self.chunks
+    }
+
+    /// Check each retrieved chunk against its entry and join them.
+    pub fn assemble(&self, chunks: Vec<Vec<u8>>) -> Result<Vec<u8>> {
+        if chunks.len() != self.chunks.len() {
+            return Err(EigenDaError::Codec(format!(
+                "manifest lists {} chunks, got {}",
+                self.chunks.len(),
+                chunks.len()
+            )));
+        }
+        // The manifest was retrieved like the chunks, so its length is only
+        // trusted once the chunks it is checked against are verified.
+        for (entry, chunk) in self.chunks.iter().zip(&chunks) {
+            entry.verify(chunk)?;
+        }
+        let length: usize = chunks.iter().map(Vec::len).sum();
+        if length as u64 != self.length {
+            return Err(EigenDaError::Codec(format!(
+                "chunks hold {} bytes, manifest says {}",
+                length, self.length
+            )));
+        }
+        Ok(chunks.concat())
+    }
+}
+
+/// Apply `f` to every item on up to `max_concurrency` threads, keeping the
+/// order of the items. Stops taking new items after the first failure.
+pub(crate) fn map_concurrent<T, R, F>(items: &[T], max_concurrency: usize, f: F) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<Result<R>>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..max_concurrency.min(items.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= items.len() || failed.load(Ordering::SeqCst) {
+                    break;
+                }
+                let result = f(&items[index]);
+                if result.is_err() {
+                    failed.store(true, Ordering::SeqCst);
+                }
+                results.lock().expect("results lock poisoned.")[index] = Some(result);
+            });
+        }
+    });
+
+    // Items are only skipped after a failure, whose error `collect` returns.
+    results
+        .into_inner()
+        .expect("results lock poisoned.")
+        .into_iter()
+        .flatten()
+        .collect()
+}
@@ -1,18 +1,57 @@
 use crate::batch::BatchHeaderHash;
-use crate::grpcurl_command;
-use crate::payload::EigenDaBlobPayload;
+use crate::blob::EncodedBlob;
+use crate::chunked::{self, ChunkedOptions, Manifest, ManifestChunk, MANIFEST_TYPE_TAG};
+use crate::compression::Compression;
+use crate::encoding::BlobEncoding;
+use crate::encryption::{EncryptionKey, KeyProvider};
+use crate::methods::EigenDaGrpcMethod;
+use crate::packer::{BlobPacker, MessageLocator};
+use crate::payload::{DisperseOptions, EigenDaBlobPayload};
+use crate::reference::BlobReference;
 use crate::response::BlobResponse;
+use crate::result::Result;
+use crate::retry::RetryPolicy;
+use crate::signer::Signer;
+use crate::state::{ConfirmationWait, ConfirmedBlob};
 use crate::status::BlobStatus;
+use crate::timeout::Timeouts;
+use crate::transport::DisperserTransport;
+use crate::typed::Format;
 use derive_builder::Builder;
-use regex::Regex;
-use std::str::FromStr;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
+/// Client for the EigenDA disperser.
+///
+/// Requests go through a [`DisperserTransport`]. The default client uses
+/// [`TonicTransport`](crate::native::TonicTransport), or `GrpcurlTransport`
+/// when the `grpcurl` feature is enabled.
 #[derive(Builder, Clone, Debug)]
 pub struct EigenDaGrpcClient {
-    grpcurl_bin_path: Option<String>,
-    proto_path: String,
-    disperser_path: String,
-    server_address: String,
+    #[builder(setter(custom))]
+    transport: Arc<dyn DisperserTransport>,
+    /// When set, blobs are dispersed with `DisperseBlobAuthenticated`.
+    #[builder(default, setter(custom))]
+    signer: Option<Arc<dyn Signer>>,
+    /// When set, failed requests are retried as the policy allows.
+    #[builder(default, setter(custom))]
+    retry_policy: Option<RetryPolicy>,
+    #[builder(default, setter(custom))]
+    timeouts: Timeouts,
+    /// How `disperse_data` and `disperse_typed` compress data.
+    #[builder(default)]
+    compression: Compression,
+    /// When set, `disperse_data` and `disperse_typed` encrypt data with
+    /// this key.
+    #[builder(default, setter(custom))]
+    encryption_key: Option<EncryptionKey>,
+    /// Resolves the keys of encrypted blobs in `retrieve_data` and
+    /// `retrieve_typed`.
+    #[builder(default, setter(custom))]
+    key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 pub const DEFAULT_EIGENDA_SERVER_ADDRESS: &str = "disperser-holesky.eigenda.xyz:443";
@@ -21,176 +60,351 @@ pub const EIGENDA_COMMON_PROTO_FILENAME: &str = "common.proto";
 pub const EIGENDA_DISPERSER_PROTO_FILENAME: &str = "disperser.proto";
 impl Default for EigenDaGrpcClient {
     fn default() -> Self {
-        let mut eigenda_proto_path = std::env::current_dir()
-            .expect("failed to get current directory for checking eigenda api path.");
-        eigenda_proto_path.push(EIGENDA_PROTO_PATH);
-
-        let mut common_path = eigenda_proto_path.clone();
-        common_path.push("common");
-        if !common_path.exists() {
-            std::fs::create_dir_all(&common_path)
-                .expect("failed to create eigenda common proto path.");
-        }
-        common_path.push(EIGENDA_COMMON_PROTO_FILENAME);
-        std::fs::write(
-            &common_path,
-            include_bytes!("../eigenda/api/proto/common/common.proto"),
-        )
-        .expect("failed to write eigenda common proto api to file.");
-
-        let mut disperser_path = eigenda_proto_path.clone();
-        disperser_path.push("disperser");
-        if !disperser_path.exists() {
-            std::fs::create_dir_all(&disperser_path)
-                .expect("failed to create eigenda disperser proto path.");
-        }
-        disperser_path.push(EIGENDA_DISPERSER_PROTO_FILENAME);
-        std::fs::write(
-            &disperser_path,
-            include_bytes!("../eigenda/api/proto/disperser/disperser.proto"),
-        )
-        .expect("failed to write eigenda disperser proto api to file.");
+        #[cfg(feature = "grpcurl")]
+        let transport = crate::grpcurl::GrpcurlTransport::default();
+        #[cfg(not(feature = "grpcurl"))]
+        let transport = crate::native::TonicTransport::default();
 
         EigenDaGrpcClientBuilder::default()
-            .grpcurl_bin_path(None)
-            .proto_path(
-                eigenda_proto_path
-                    .to_str()
-                    .expect("failed to convert eigenda proto path to &str")
-                    .to_string(),
-            )
-            .disperser_path(
-                disperser_path
-                    .to_str()
-                    .expect("failed to convert eigenda disperser proto path to &str")
-                    .to_string(),
-            )
-            .server_address(DEFAULT_EIGENDA_SERVER_ADDRESS.to_string())
+            .transport(transport)
             .build()
             .expect("failed to build eigenda gRPC client.")
     }
 }
 
+impl EigenDaGrpcClientBuilder {
+    pub fn transport<T: DisperserTransport + 'static>(&mut self, transport: T) -> &mut Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn signer<S: Signer + 'static>(&mut self, signer: S) -> &mut Self {
+        self.signer = Some(Some(Arc::new(signer)));
+        self
+    }
+
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = Some(Some(retry_policy));
+        self
+    }
+
+    pub fn encryption_key(&mut self, encryption_key: EncryptionKey) -> &mut Self {
+        self.encryption_key = Some(Some(encryption_key));
+        self
+    }
+
+    pub fn key_provider<P: KeyProvider + 'static>(&mut self, key_provider: P) -> &mut Self {
+        self.key_provider = Some(Some(Arc::new(key_provider)));
+        self
+    }
+
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeouts = Some(self.timeouts.unwrap_or_default().with_connect(timeout));
+        self
+    }
+
+    pub fn rpc_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeouts = Some(self.timeouts.unwrap_or_default().with_rpc(timeout));
+        self
+    }
+
+    /// Use the default transport, sending to `address`.
+    #[deprecated(note = "pass a transport built for the address to `transport` instead")]
+    pub fn server_address(&mut self, address: String) -> &mut Self {
+        #[cfg(feature = "grpcurl")]
+        let transport = {
+            let mut transport = crate::grpcurl::GrpcurlTransport::default();
+            transport.update_server_address(address);
+            transport
+        };
+        #[cfg(not(feature = "grpcurl"))]
+        let transport = crate::native::TonicTransport::new(address);
+        self.transport(transport)
+    }
+}
+
 impl EigenDaGrpcClient {
-    /// Update the EigenDA server address with some URL address other than [`DEFAULT_EIGENDA_SERVER_ADDRESS`].
+    pub fn transport(&self) -> &Arc<dyn DisperserTransport> {
+        &self.transport
+    }
+
+    /// Send later requests to the disperser at `address`. Fails if the
+    /// transport cannot be pointed at another address.
+    pub fn set_server_address(&mut self, address: &str) -> Result<()> {
+        self.transport = self.transport.with_server_address(address)?;
+        Ok(())
+    }
+
+    /// Panics if the transport cannot be pointed at another address, which
+    /// the transports of this crate all can.
+    #[deprecated(note = "use `set_server_address`")]
     pub fn update_server_address(&mut self, address: String) {
-        self.server_address = address;
+        self.set_server_address(&address)
+            .expect("transport does not support changing the server address.");
     }
 
+    /// Panics if the transport is not a `GrpcurlTransport`.
+    #[cfg(feature = "grpcurl")]
+    #[deprecated(note = "build the client with a `GrpcurlTransport` using the path instead")]
     pub fn update_grpcurl_bin_path(&mut self, path: Option<String>) {
-        self.grpcurl_bin_path = path;
+        self.transport = self
+            .transport
+            .with_grpcurl_bin_path(path)
+            .expect("transport does not run grpcurl.");
     }
 
-    fn get_payload(&self, encoded_data: String) -> EigenDaBlobPayload {
-        EigenDaBlobPayload::new(encoded_data)
+    pub fn signer(&self) -> Option<&Arc<dyn Signer>> {
+        self.signer.as_ref()
     }
 
-    pub const DISPERSE_BLOB: &'static str = "disperser.Disperser/DisperseBlob";
-    pub fn disperse_blob(&self, encoded_data: String) -> Result<BlobResponse, std::io::Error> {
-        let payload: String = self.get_payload(encoded_data).into();
-
-        let output = grpcurl_command!(
-            &self.grpcurl_bin_path.as_ref(),
-            "-import-path",
-            &self.proto_path,
-            "-proto",
-            &self.disperser_path,
-            "-d",
-            &payload,
-            &self.server_address,
-            Self::DISPERSE_BLOB
-        )?;
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
 
-        if output.status.success() {
-            let response: BlobResponse = String::from_utf8(output.stdout)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?
-                .into();
-            Ok(response)
-        } else {
-            let error_message = String::from_utf8(output.stderr)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                error_message,
-            ))
+    pub fn encryption_key(&self) -> Option<&EncryptionKey> {
+        self.encryption_key.as_ref()
+    }
+
+    pub fn key_provider(&self) -> Option<&Arc<dyn KeyProvider>> {
+        self.key_provider.as_ref()
+    }
+
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    /// A client sharing this one's transport, whose requests use the
+    /// timeouts set in `timeouts` instead of its own.
+    pub fn with_timeouts(&self, timeouts: Timeouts) -> Self {
+        let mut client = self.clone();
+        client.timeouts = self.timeouts.merge(&timeouts);
+        client
+    }
+
+    fn send<T, F>(&self, method: EigenDaGrpcMethod, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Result<T>,
+    {
+        match &self.retry_policy {
+            Some(retry_policy) => retry_policy.run(method, request),
+            None => request(),
         }
     }
 
-    pub const GET_BLOB_STATUS: &'static str = "disperser.Disperser/GetBlobStatus";
-    pub fn get_blob_status(&self, request_id: &str) -> Result<BlobStatus, std::io::Error> {
-        let payload = serde_json::json!({
-            "request_id": request_id
-        });
-
-        let output = grpcurl_command!(
-            &self.grpcurl_bin_path.as_ref(),
-            "-import-path",
-            &self.proto_path,
-            "-proto",
-            &self.disperser_path,
-            "-d",
-            &payload.to_string(),
-            &self.server_address,
-            Self::GET_BLOB_STATUS
-        )?;
+    fn encoding(&self) -> BlobEncoding<'_> {
+        BlobEncoding {
+            compression: self.compression,
+            encryption_key: self.encryption_key.as_ref(),
+            key_provider: self.key_provider.as_deref(),
+        }
+    }
 
-        if output.status.success() {
-            let response = String::from_utf8(output.stdout)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
-            let re = Regex::new(r"(\\n|\\t|\n\t|\s\s+)").unwrap();
-            let clean_response = re.replace_all(&response, " ").to_string();
-            let res = BlobStatus::from_str(&clean_response);
-            if let Err(e) = &res {
-                dbg!(response);
-                log::error!("{}", e);
-            }
-            Ok(res?)
-        } else {
-            let error_message = String::from_utf8(output.stderr)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                error_message,
-            ))
+    pub const DISPERSE_BLOB: &'static str = "disperser.Disperser/DisperseBlob";
+    pub fn disperse_blob(&self, encoded_data: String) -> Result<BlobResponse> {
+        self.disperse_blob_with_options(encoded_data, DisperseOptions::default())
+    }
+
+    /// Disperse a blob with custom quorums or an account id, which are
+    /// validated before the request is sent.
+    pub fn disperse_blob_with_options(
+        &self,
+        encoded_data: String,
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let payload = EigenDaBlobPayload::validated(encoded_data, options)?;
+        match &self.signer {
+            Some(signer) => self.send(EigenDaGrpcMethod::DisperseBlobAuthenticated, || {
+                self.transport.disperse_blob_authenticated(
+                    &payload,
+                    signer.as_ref(),
+                    &self.timeouts,
+                )
+            }),
+            None => self.send(EigenDaGrpcMethod::DisperseBlob, || {
+                self.transport.disperse_blob(&payload, &self.timeouts)
+            }),
         }
     }
 
+    pub const GET_BLOB_STATUS: &'static str = "disperser.Disperser/GetBlobStatus";
+    pub fn get_blob_status(&self, request_id: &str) -> Result<BlobStatus> {
+        self.send(EigenDaGrpcMethod::GetBlobStatus, || {
+            self.transport.get_blob_status(request_id, &self.timeouts)
+        })
+    }
+
     pub const RETRIEVE_BLOB: &'static str = "disperser.Disperser/RetrieveBlob";
     pub fn retrieve_blob(
         &self,
         batch_header_hash: &BatchHeaderHash,
-        blob_index: u128,
-    ) -> Result<String, std::io::Error> {
-        let payload = serde_json::json!({
-            "batch_header_hash": batch_header_hash.to_string(),
-            "blob_index": blob_index.to_string()
-        });
-
-        let output = grpcurl_command!(
-            &self.grpcurl_bin_path.as_ref(),
-            "-import-path",
-            &self.proto_path,
-            "-proto",
-            &self.disperser_path,
-            "-d",
-            &payload.to_string(),
-            &self.server_address,
-            Self::RETRIEVE_BLOB
-        )?;
+        blob_index: u32,
+    ) -> Result<EncodedBlob> {
+        self.send(EigenDaGrpcMethod::RetrieveBlob, || {
+            self.transport
+                .retrieve_blob(batch_header_hash, blob_index, &self.timeouts)
+        })
+    }
+
+    /// Disperse arbitrary bytes, wrapped in an [`envelope`](crate::envelope)
+    /// so every field element of the blob is valid and the data can be
+    /// retrieved exactly.
+    pub fn disperse_data(&self, data: &[u8]) -> Result<BlobResponse> {
+        self.disperse_data_with_options(data, DisperseOptions::default())
+    }
+
+    pub fn disperse_data_with_options(
+        &self,
+        data: &[u8],
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let blob = self.encoding().encode(data, Format::Raw)?;
+        self.disperse_blob_with_options(blob, options)
+    }
 
-        if output.status.success() {
-            let response = String::from_utf8(output.stdout)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
+    /// Retrieve bytes dispersed with [`EigenDaGrpcClient::disperse_data`].
+    /// Fails if the blob is not an envelope this crate can read; blobs
+    /// dispersed without one can be read with [`EigenDaGrpcClient::retrieve_blob`]
+    /// and [`DecodedBlob::from_raw`](crate::blob::DecodedBlob::from_raw).
+    pub fn retrieve_data(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+    ) -> Result<Vec<u8>> {
+        let blob = self.retrieve_blob(batch_header_hash, blob_index)?;
+        Ok(self.encoding().decode(blob)?.data())
+    }
 
-            Ok(response)
-        } else {
-            let error_message = String::from_utf8(output.stderr)
-                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
+    /// Disperse `value` serialized in `format`, tagged with `type_tag` so
+    /// readers can check what they decode.
+    pub fn disperse_typed<T: Serialize>(
+        &self,
+        value: &T,
+        format: Format,
+        type_tag: &str,
+    ) -> Result<BlobResponse> {
+        self.disperse_typed_with_options(value, format, type_tag, DisperseOptions::default())
+    }
 
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                error_message,
-            ))
+    pub fn disperse_typed_with_options<T: Serialize>(
+        &self,
+        value: &T,
+        format: Format,
+        type_tag: &str,
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let blob = self.encoding().encode_typed(value, format, type_tag)?;
+        self.disperse_blob_with_options(blob, options)
+    }
+
+    /// Retrieve a record dispersed with [`EigenDaGrpcClient::disperse_typed`],
+    /// failing with [`EigenDaError::TypeMismatch`](crate::error::EigenDaError::TypeMismatch)
+    /// if it was tagged with another type.
+    pub fn retrieve_typed<T: DeserializeOwned>(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        type_tag: &str,
+    ) -> Result<T> {
+        let blob = self.retrieve_blob(batch_header_hash, blob_index)?;
+        self.encoding().decode(blob)?.deserialize(type_tag)
+    }
+
+    /// Poll the status of `request_id` every `poll_interval` until the blob
+    /// is confirmed, failing if it fails or `timeout` passes first. A status
+    /// that is neither pending nor final, such as `UNKNOWN`, fails too
+    /// rather than being polled forever.
+    pub fn wait_for_confirmation(
+        &self,
+        request_id: &str,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<ConfirmedBlob> {
+        let wait = ConfirmationWait::new(request_id, timeout);
+        loop {
+            if let Some(blob) = wait.check(self.get_blob_status(request_id)?)? {
+                return Ok(blob);
+            }
+            thread::sleep(poll_interval);
         }
     }
+
+    fn confirm(&self, response: &BlobResponse, options: &ChunkedOptions) -> Result<BlobReference> {
+        let blob = self.wait_for_confirmation(
+            &response.request_id(),
+            options.poll_interval(),
+            options.confirmation_timeout(),
+        )?;
+        Ok(blob.reference())
+    }
+
+    /// Disperse `data` of any size as chunks followed by a
+    /// [`Manifest`](crate::chunked::Manifest) of them, waiting for every
+    /// blob to be confirmed. Returns the reference of the manifest, from
+    /// which [`EigenDaGrpcClient::retrieve_chunked`] reassembles the data.
+    pub fn disperse_chunked(&self, data: &[u8], options: &ChunkedOptions) -> Result<BlobReference> {
+        options.validate()?;
+        let chunks: Vec<&[u8]> = data.chunks(options.chunk_size()).collect();
+        let entries = chunked::map_concurrent(&chunks, options.max_concurrency(), |chunk| {
+            let response =
+                self.disperse_data_with_options(chunk, options.disperse_options().clone())?;
+            Ok(ManifestChunk::new(self.confirm(&response, options)?, chunk))
+        })?;
+
+        let blob = self.encoding().encode_manifest(entries)?;
+        let response = self.disperse_blob_with_options(blob, options.disperse_options().clone())?;
+        self.confirm(&response, options)
+    }
+
+    /// Retrieve data dispersed with [`EigenDaGrpcClient::disperse_chunked`]
+    /// from the reference of its manifest, checking every chunk against the
+    /// hash the manifest lists.
+    pub fn retrieve_chunked(
+        &self,
+        manifest: &BlobReference,
+        options: &ChunkedOptions,
+    ) -> Result<Vec<u8>> {
+        options.validate()?;
+        let manifest: Manifest = self.retrieve_typed(
+            manifest.batch_header_hash(),
+            manifest.blob_index(),
+            MANIFEST_TYPE_TAG,
+        )?;
+        let chunks =
+            chunked::map_concurrent(manifest.chunks(), options.max_concurrency(), |entry| {
+                let reference = entry.reference();
+                self.retrieve_data(reference.batch_header_hash(), reference.blob_index())
+            })?;
+        manifest.assemble(chunks)
+    }
+
+    /// Disperse the messages collected by `packer` as one blob. Once it is
+    /// confirmed, [`BlobPacker::locators`] gives the locator of each
+    /// message.
+    pub fn disperse_packed(&self, packer: &BlobPacker) -> Result<BlobResponse> {
+        self.disperse_packed_with_options(packer, DisperseOptions::default())
+    }
+
+    pub fn disperse_packed_with_options(
+        &self,
+        packer: &BlobPacker,
+        options: DisperseOptions,
+    ) -> Result<BlobResponse> {
+        let blob = self.encoding().encode_packed(packer)?;
+        self.disperse_blob_with_options(blob, options)
+    }
+
+    /// Retrieve a single message dispersed with `disperse_packed`.
+    pub fn retrieve_message(&self, locator: &MessageLocator) -> Result<Vec<u8>> {
+        let reference = locator.blob();
+        let blob = self.retrieve_blob(reference.batch_header_hash(), reference.blob_index())?;
+        Ok(self
+            .encoding()
+            .decode(blob)?
+            .message(locator.index())?
+            .to_vec())
+    }
 }
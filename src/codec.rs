@@ -0,0 +1,63 @@
+//! Encoding of arbitrary bytes into blobs the disperser accepts.
+//!
+//! The disperser reads a blob as a sequence of 32-byte big-endian BN254
+//! field elements and rejects it if any of them is not below the field
+//! modulus. [`encode`] splits the data into 31-byte chunks and prefixes each
+//! with a zero byte, which keeps every element below the modulus.
+use crate::error::EigenDaError;
+use crate::result::Result;
+
+/// The BN254 scalar field modulus, big-endian. The disperser rejects blobs
+/// where any 32-byte chunk is not below it.
+pub const BN254_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// Bytes in a field element of a blob.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+/// Data bytes [`encode`] stores in each field element.
+pub const BYTES_PER_CHUNK: usize = BYTES_PER_FIELD_ELEMENT - 1;
+
+/// Whether every 32-byte chunk of `data`, the last one zero-padded, is a
+/// valid field element.
+pub fn is_field_element_safe(data: &[u8]) -> bool {
+    data.chunks(BYTES_PER_FIELD_ELEMENT).all(|chunk| {
+        let mut element = [0u8; BYTES_PER_FIELD_ELEMENT];
+        element[..chunk.len()].copy_from_slice(chunk);
+        element < BN254_FIELD_MODULUS
+    })
+}
+
+/// The length of `len` bytes of data once encoded.
+pub fn encoded_len(len: usize) -> usize {
+    len + len.div_ceil(BYTES_PER_CHUNK)
+}
+
+/// Encode `data` into field-element-safe form, a zero byte followed by up
+/// to 31 data bytes per field element. The last element is not padded.
+pub fn encode(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(encoded_len(data.len()));
+    for chunk in data.chunks(BYTES_PER_CHUNK) {
+        encoded.push(0);
+        encoded.extend_from_slice(chunk);
+    }
+    encoded
+}
+
+/// The inverse of [`encode`]. Padding the disperser appended to a retrieved
+/// blob is decoded as trailing zero bytes.
+pub fn decode(encoded: &[u8]) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(encoded.len());
+    for (index, element) in encoded.chunks(BYTES_PER_FIELD_ELEMENT).enumerate() {
+        if element[0] != 0 {
+            return Err(EigenDaError::Codec(format!(
+                "field element {} does not start with a zero byte",
+                index
+            )));
+        }
+        data.extend_from_slice(&element[1..]);
+    }
+    Ok(data)
+}
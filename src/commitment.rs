@@ -1,11 +1,208 @@
+//! The KZG commitment of a blob, a point on the G1 curve of BN254.
+use crate::bytes;
+use crate::error::EigenDaError;
+use crate::result::Result;
+use crypto_bigint::modular::constant_mod::ResidueParams;
+use crypto_bigint::{const_residue, impl_modulus, U256};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct BlobCommitment(String);
+/// The modulus of the BN254 base field, which G1 coordinates belong to,
+/// big-endian.
+pub const BN254_BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
 
-impl ToString for BlobCommitment {
-    fn to_string(&self) -> String {
-        self.0.clone()
+/// Bytes in the uncompressed form of a G1 point, `x || y`.
+pub const G1_POINT_LEN: usize = 64;
+
+impl_modulus!(
+    Bn254BaseField,
+    U256,
+    "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47"
+);
+
+/// An element of the BN254 base field, 32 bytes big-endian.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FieldElement([u8; 32]);
+
+impl FieldElement {
+    /// Parse a big-endian element of up to 32 bytes, rejecting values not
+    /// below [`BN254_BASE_FIELD_MODULUS`].
+    pub fn from_be_bytes(value: &[u8]) -> Result<Self> {
+        if value.len() > 32 {
+            return Err(EigenDaError::Codec(format!(
+                "field element of {} bytes is longer than 32",
+                value.len()
+            )));
+        }
+        let mut bytes = [0u8; 32];
+        bytes[32 - value.len()..].copy_from_slice(value);
+        if bytes >= BN254_BASE_FIELD_MODULUS {
+            return Err(EigenDaError::Codec(format!(
+                "{} is not below the BN254 base field modulus",
+                bytes::to_hex(&bytes)
+            )));
+        }
+        Ok(FieldElement(bytes))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 32]
+    }
+}
+
+impl std::fmt::Display for FieldElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", bytes::to_hex(&self.0))
+    }
+}
+
+/// A point on the G1 curve of BN254, `y² = x³ + 3`. The point at infinity
+/// is `(0, 0)`, as in the Ethereum precompiles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct G1Point {
+    x: FieldElement,
+    y: FieldElement,
+}
+
+impl G1Point {
+    /// The point `(x, y)`, failing if it is not on the curve.
+    pub fn new(x: FieldElement, y: FieldElement) -> Result<Self> {
+        let point = G1Point { x, y };
+        if !point.is_on_curve() {
+            return Err(EigenDaError::Codec(format!(
+                "({}, {}) is not on the BN254 G1 curve",
+                x, y
+            )));
+        }
+        Ok(point)
+    }
+
+    /// Parse the 64-byte uncompressed form, `x || y` big-endian.
+    pub fn from_bytes(value: &[u8]) -> Result<Self> {
+        if value.len() != G1_POINT_LEN {
+            return Err(EigenDaError::Codec(format!(
+                "G1 point of {} bytes, expected {}",
+                value.len(),
+                G1_POINT_LEN
+            )));
+        }
+        G1Point::new(
+            FieldElement::from_be_bytes(&value[..32])?,
+            FieldElement::from_be_bytes(&value[32..])?,
+        )
+    }
+
+    pub fn x(&self) -> &FieldElement {
+        &self.x
+    }
+
+    pub fn y(&self) -> &FieldElement {
+        &self.y
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero()
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        if self.is_infinity() {
+            return true;
+        }
+        let x = U256::from_be_slice(&self.x.0);
+        let y = U256::from_be_slice(&self.y.0);
+        let three = U256::from_u8(3);
+        let x = const_residue!(x, Bn254BaseField);
+        let y = const_residue!(y, Bn254BaseField);
+        let three = const_residue!(three, Bn254BaseField);
+        y.square() == x.square() * x + three
+    }
+
+    /// The 64-byte uncompressed form, `x || y` big-endian, that verifier
+    /// contracts take.
+    pub fn to_bytes(&self) -> [u8; G1_POINT_LEN] {
+        let mut bytes = [0u8; G1_POINT_LEN];
+        bytes[..32].copy_from_slice(&self.x.0);
+        bytes[32..].copy_from_slice(&self.y.0);
+        bytes
+    }
+}
+
+/// The grpcurl JSON form of `common.G1Commitment`, base64 coordinates.
+#[derive(Serialize, Deserialize)]
+struct G1CommitmentJson {
+    #[serde(with = "crate::bytes::base64_serde")]
+    x: Vec<u8>,
+    #[serde(with = "crate::bytes::base64_serde")]
+    y: Vec<u8>,
+}
+
+/// The KZG commitment of a blob. It is checked to be on the curve when
+/// parsed, and serializes to the `{"x", "y"}` base64 form grpcurl renders
+/// `common.G1Commitment` in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlobCommitment(G1Point);
+
+impl BlobCommitment {
+    pub fn new(point: G1Point) -> Self {
+        BlobCommitment(point)
+    }
+
+    /// Parse the 64-byte uncompressed form, `x || y` big-endian.
+    pub fn from_bytes(value: &[u8]) -> Result<Self> {
+        G1Point::from_bytes(value).map(BlobCommitment)
+    }
+
+    pub fn point(&self) -> &G1Point {
+        &self.0
+    }
+
+    pub fn x(&self) -> &FieldElement {
+        self.0.x()
+    }
+
+    pub fn y(&self) -> &FieldElement {
+        self.0.y()
+    }
+
+    /// The 64-byte uncompressed form rollup contracts take.
+    pub fn to_bytes(&self) -> [u8; G1_POINT_LEN] {
+        self.0.to_bytes()
+    }
+}
+
+impl Serialize for BlobCommitment {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        G1CommitmentJson {
+            x: self.x().to_be_bytes().to_vec(),
+            y: self.y().to_be_bytes().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BlobCommitment {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let json = G1CommitmentJson::deserialize(deserializer)?;
+        let point = FieldElement::from_be_bytes(&json.x)
+            .and_then(|x| G1Point::new(x, FieldElement::from_be_bytes(&json.y)?))
+            .map_err(serde::de::Error::custom)?;
+        Ok(BlobCommitment(point))
+    }
+}
+
+impl std::fmt::Display for BlobCommitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x(), self.y())
     }
 }
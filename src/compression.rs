@@ -0,0 +1,137 @@
+//! Compression of dispersed data, applied before it is encoded into field
+//! elements. Each algorithm is behind the cargo feature of the same name;
+//! blobs compressed with an algorithm that is not compiled in fail to
+//! decode with an error naming the feature.
+use crate::error::EigenDaError;
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+
+/// The largest size data is decompressed to by default, guarding against
+/// blobs that decompress to far more than they hold.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// The compression algorithm of dispersed data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Compression {
+    #[default]
+    None = 0,
+    /// Requires the `zstd` feature.
+    Zstd = 1,
+    /// Requires the `gzip` feature.
+    Gzip = 2,
+    /// Requires the `lz4` feature.
+    Lz4 = 3,
+}
+
+impl TryFrom<u8> for Compression {
+    type Error = EigenDaError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Zstd),
+            2 => Ok(Compression::Gzip),
+            3 => Ok(Compression::Lz4),
+            _ => Err(EigenDaError::Codec(format!(
+                "unknown compression {}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Compression {
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                zstd::encode_all(data, zstd::DEFAULT_COMPRESSION_LEVEL).map_err(compression_error)
+            }
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                use std::io::Write;
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data).map_err(compression_error)?;
+                encoder.finish().map_err(compression_error)
+            }
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => Ok(lz4_flex::compress_prepend_size(data)),
+            #[cfg(not(all(feature = "zstd", feature = "gzip", feature = "lz4")))]
+            _ => Err(self.not_compiled_in()),
+        }
+    }
+
+    /// Decompress `data`, failing if it decompresses to more than
+    /// `max_size` bytes.
+    #[cfg_attr(
+        not(any(feature = "zstd", feature = "gzip", feature = "lz4")),
+        allow(unused_variables)
+    )]
+    pub fn decompress(&self, data: &[u8], max_size: usize) -> Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                let decoder = zstd::stream::read::Decoder::new(data).map_err(compression_error)?;
+                read_limited(decoder, max_size)
+            }
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => read_limited(flate2::read::GzDecoder::new(data), max_size),
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => {
+                // The size prefix is checked before anything is allocated.
+                let size = data
+                    .get(..4)
+                    .map(|size| u32::from_le_bytes(size.try_into().expect("slice of 4 bytes.")))
+                    .ok_or_else(|| EigenDaError::Codec("lz4 data is truncated".to_string()))?;
+                if size as usize > max_size {
+                    return Err(too_large(max_size));
+                }
+                lz4_flex::decompress_size_prepended(data).map_err(compression_error)
+            }
+            #[cfg(not(all(feature = "zstd", feature = "gzip", feature = "lz4")))]
+            _ => Err(self.not_compiled_in()),
+        }
+    }
+
+    #[cfg(not(all(feature = "zstd", feature = "gzip", feature = "lz4")))]
+    fn not_compiled_in(&self) -> EigenDaError {
+        let feature = match self {
+            Compression::None => "",
+            Compression::Zstd => "zstd",
+            Compression::Gzip => "gzip",
+            Compression::Lz4 => "lz4",
+        };
+        EigenDaError::Codec(format!(
+            "{:?} compression requires the `{}` feature",
+            self, feature
+        ))
+    }
+}
+
+#[cfg(any(feature = "zstd", feature = "gzip"))]
+fn read_limited<R: std::io::Read>(reader: R, max_size: usize) -> Result<Vec<u8>> {
+    use std::io::Read;
+    let mut data = Vec::new();
+    reader
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut data)
+        .map_err(compression_error)?;
+    if data.len() > max_size {
+        return Err(too_large(max_size));
+    }
+    Ok(data)
+}
+
+#[cfg(any(feature = "zstd", feature = "gzip", feature = "lz4"))]
+fn too_large(max_size: usize) -> EigenDaError {
+    EigenDaError::Codec(format!("data decompresses to more than {} bytes", max_size))
+}
+
+#[cfg(any(feature = "zstd", feature = "gzip", feature = "lz4"))]
+fn compression_error<E: std::fmt::Display>(err: E) -> EigenDaError {
+    EigenDaError::Codec(err.to_string())
+}
@@ -0,0 +1,51 @@
+//! How the clients turn data into blobs and back. The blocking and async
+//! clients only differ in how they send requests, so both encode and
+//! decode through here.
+use crate::blob::{DecodedBlob, EncodedBlob};
+use crate::chunked::{Manifest, ManifestChunk, MANIFEST_TYPE_TAG};
+use crate::compression::{Compression, DEFAULT_MAX_DECOMPRESSED_SIZE};
+use crate::encryption::{EncryptionKey, KeyProvider};
+use crate::envelope;
+use crate::packer::BlobPacker;
+use crate::result::Result;
+use crate::typed::{self, Format};
+use serde::Serialize;
+
+/// The compression and encryption settings of a client.
+pub(crate) struct BlobEncoding<'a> {
+    pub compression: Compression,
+    pub encryption_key: Option<&'a EncryptionKey>,
+    pub key_provider: Option<&'a dyn KeyProvider>,
+}
+
+impl BlobEncoding<'_> {
+    /// `data` serialized in `format`, wrapped in an envelope and base64
+    /// encoded, ready to disperse.
+    pub fn encode(&self, data: &[u8], format: Format) -> Result<String> {
+        let blob = envelope::encode_with(data, format, self.compression, self.encryption_key)?;
+        Ok(base64::encode(blob))
+    }
+
+    pub fn encode_typed<T: Serialize>(
+        &self,
+        value: &T,
+        format: Format,
+        type_tag: &str,
+    ) -> Result<String> {
+        self.encode(&typed::serialize(value, format, type_tag)?, format)
+    }
+
+    pub fn encode_packed(&self, packer: &BlobPacker) -> Result<String> {
+        self.encode(&packer.pack(), Format::Packed)
+    }
+
+    /// The manifest of chunked data, listing `entries` in order.
+    pub fn encode_manifest(&self, entries: Vec<ManifestChunk>) -> Result<String> {
+        self.encode_typed(&Manifest::new(entries), Format::Json, MANIFEST_TYPE_TAG)
+    }
+
+    /// Unwrap a retrieved envelope, decrypting it with the client's keys.
+    pub fn decode(&self, blob: EncodedBlob) -> Result<DecodedBlob> {
+        DecodedBlob::from_encoded_with(blob, DEFAULT_MAX_DECOMPRESSED_SIZE, self.key_provider)
+    }
+}
@@ -0,0 +1,213 @@
+//! Client-side encryption of dispersed data, for data that must not be
+//! readable on EigenDA.
+//!
+//! Data is encrypted with ChaCha20-Poly1305 after it is compressed. The
+//! encrypted data starts with the id of the key, length-prefixed, and the
+//! nonce, so a reader can look the key up with a [`KeyProvider`]:
+//!
+//! `[key id length u8][key id][nonce, 12 bytes][ciphertext and tag]`
+//!
+//! The key id and the caller's associated data, such as the envelope
+//! header, are authenticated along with the data.
+use crate::error::EigenDaError;
+use crate::result::Result;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Bytes in a key.
+pub const KEY_LEN: usize = 32;
+
+/// Bytes in the nonce stored with encrypted data.
+pub const NONCE_LEN: usize = 12;
+
+/// The longest key id that can be stored with encrypted data.
+pub const MAX_KEY_ID_LEN: usize = u8::MAX as usize;
+
+/// A ChaCha20-Poly1305 key.
+pub type Key = [u8; KEY_LEN];
+
+/// How dispersed data is encrypted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Encryption {
+    #[default]
+    None = 0,
+    ChaCha20Poly1305 = 1,
+}
+
+impl TryFrom<u8> for Encryption {
+    type Error = EigenDaError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Encryption::None),
+            1 => Ok(Encryption::ChaCha20Poly1305),
+            _ => Err(EigenDaError::Codec(format!("unknown encryption {}", value))),
+        }
+    }
+}
+
+/// Resolves the key ids stored with encrypted blobs to keys.
+pub trait KeyProvider: std::fmt::Debug + Send + Sync {
+    /// The key with id `key_id`, failing with
+    /// [`EigenDaError::Encryption`] if it is unknown.
+    fn key(&self, key_id: &str) -> Result<Key>;
+}
+
+/// A [`KeyProvider`] holding its keys in memory.
+#[derive(Clone, Default)]
+pub struct KeyRing {
+    keys: HashMap<String, Key>,
+}
+
+impl KeyRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key<I: Into<String>>(mut self, key_id: I, key: Key) -> Self {
+        self.insert(key_id, key);
+        self
+    }
+
+    pub fn insert<I: Into<String>>(&mut self, key_id: I, key: Key) {
+        self.keys.insert(key_id.into(), key);
+    }
+}
+
+impl std::fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyRing")
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyProvider for KeyRing {
+    fn key(&self, key_id: &str) -> Result<Key> {
+        self.keys
+            .get(key_id)
+            .copied()
+            .ok_or_else(|| EigenDaError::Encryption(format!("unknown key id {:?}", key_id)))
+    }
+}
+
+/// The key data is encrypted with before it is dispersed, along with the
+/// id readers look it up by.
+#[derive(Clone)]
+pub struct EncryptionKey {
+    key_id: String,
+    key: Key,
+}
+
+impl EncryptionKey {
+    pub fn new<I: Into<String>>(key_id: I, key: Key) -> Result<Self> {
+        let key_id = key_id.into();
+        if key_id.len() > MAX_KEY_ID_LEN {
+            return Err(EigenDaError::validation(format!(
+                "key id of {} bytes exceeds the maximum of {}",
+                key_id.len(),
+                MAX_KEY_ID_LEN
+            )));
+        }
+        Ok(EncryptionKey { key_id, key })
+    }
+
+    /// Look up `key_id` in `keys`.
+    pub fn from_provider(key_id: &str, keys: &dyn KeyProvider) -> Result<Self> {
+        EncryptionKey::new(key_id, keys.key(key_id)?)
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Encrypt `data` under a fresh random nonce, prefixed with the key id
+    /// and the nonce. The key id and `associated_data` are authenticated
+    /// along with the data, and `associated_data` must be passed again to
+    /// [`decrypt`].
+    pub fn encrypt(&self, data: &[u8], associated_data: &[u8]) -> Result<Vec<u8>> {
+        let mut encrypted = Vec::with_capacity(1 + self.key_id.len() + NONCE_LEN + data.len());
+        encrypted.push(self.key_id.len() as u8);
+        encrypted.extend_from_slice(self.key_id.as_bytes());
+
+        let cipher = ChaCha20Poly1305::new(&self.key.into());
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: data,
+                    aad: &[&encrypted[..], associated_data].concat(),
+                },
+            )
+            .map_err(|_| EigenDaError::Encryption("failed to encrypt data".to_string()))?;
+
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend(ciphertext);
+        Ok(encrypted)
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionKey")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The key id stored at the start of encrypted data, and the rest of it.
+pub fn split_key_id(encrypted: &[u8]) -> Result<(String, &[u8])> {
+    let (&len, rest) = encrypted
+        .split_first()
+        .ok_or_else(|| EigenDaError::Codec("encrypted data is empty".to_string()))?;
+    let len = len as usize;
+    if rest.len() < len {
+        return Err(EigenDaError::Codec(format!(
+            "key id of {} bytes is longer than the {} bytes left",
+            len,
+            rest.len()
+        )));
+    }
+    let (key_id, rest) = rest.split_at(len);
+    let key_id = String::from_utf8(key_id.to_vec())
+        .map_err(|err| EigenDaError::Codec(format!("key id is not utf-8: {}", err)))?;
+    Ok((key_id, rest))
+}
+
+/// Decrypt data written by [`EncryptionKey::encrypt`] with the same
+/// `associated_data`, with the key `keys` holds for its key id.
+pub fn decrypt(
+    encrypted: &[u8],
+    keys: &dyn KeyProvider,
+    associated_data: &[u8],
+) -> Result<Vec<u8>> {
+    let (key_id, rest) = split_key_id(encrypted)?;
+    let prefix = &encrypted[..encrypted.len() - rest.len()];
+    if rest.len() < NONCE_LEN {
+        return Err(EigenDaError::Codec(
+            "encrypted data is too short for a nonce".to_string(),
+        ));
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = keys.key(&key_id)?;
+    let cipher = ChaCha20Poly1305::new(&key.into());
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &[prefix, associated_data].concat(),
+            },
+        )
+        .map_err(|_| {
+            EigenDaError::Encryption(format!(
+                "failed to decrypt data with key id {:?}, the key is wrong or the data was altered",
+                key_id
+            ))
+        })
+}
@@ -0,0 +1,256 @@
+//! The versioned header written in front of data dispersed with
+//! [`EigenDaGrpcClient::disperse_data`](crate::client::EigenDaGrpcClient::disperse_data).
+//!
+//! The disperser pads a blob to whole field elements, so the data alone does
+//! not tell where it ends. The envelope starts with a 32-byte header, itself
+//! a valid field element, recording how the rest of the blob is encoded and
+//! the length of the original data:
+//!
+//! | byte   | content                              |
+//! |--------|--------------------------------------|
+//! | 0      | `0x00`                               |
+//! | 1      | envelope version, [`ENVELOPE_VERSION`] |
+//! | 2      | codec id, a [`CodecId`]              |
+//! | 3..7   | stored data length, u32 big-endian   |
+//! | 7      | serialization format, a [`Format`]   |
+//! | 8      | compression, a [`Compression`]       |
+//! | 9      | encryption, an [`Encryption`]        |
+//! | 10..32 | reserved, zero                       |
+//!
+//! Data is compressed, then encrypted, then encoded. The stored length is
+//! that of the data as it is after encryption. Encryption authenticates the
+//! header too, with its length left zero.
+use crate::codec;
+use crate::compression::{Compression, DEFAULT_MAX_DECOMPRESSED_SIZE};
+use crate::encryption::{self, Encryption, EncryptionKey, KeyProvider};
+use crate::error::EigenDaError;
+use crate::result::Result;
+use crate::typed::Format;
+
+/// The envelope version this crate writes, and the only one it reads.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// Bytes in the envelope header.
+pub const ENVELOPE_HEADER_LEN: usize = codec::BYTES_PER_FIELD_ELEMENT;
+
+/// How the data after the envelope header is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CodecId {
+    /// [`codec::encode`], 31 data bytes per field element.
+    FieldElementPadding = 0,
+}
+
+impl TryFrom<u8> for CodecId {
+    type Error = EigenDaError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(CodecId::FieldElementPadding),
+            _ => Err(EigenDaError::Codec(format!("unknown codec id {}", value))),
+        }
+    }
+}
+
+/// The header at the start of an envelope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnvelopeHeader {
+    version: u8,
+    codec: CodecId,
+    length: u32,
+    format: Format,
+    compression: Compression,
+    encryption: Encryption,
+}
+
+impl EnvelopeHeader {
+    pub fn new(
+        codec: CodecId,
+        length: u32,
+        format: Format,
+        compression: Compression,
+        encryption: Encryption,
+    ) -> Self {
+        EnvelopeHeader {
+            version: ENVELOPE_VERSION,
+            codec,
+            length,
+            format,
+            compression,
+            encryption,
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn codec(&self) -> CodecId {
+        self.codec
+    }
+
+    /// The length of the data before it was encoded, after compression and
+    /// encryption.
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub fn encryption(&self) -> Encryption {
+        self.encryption
+    }
+
+    pub fn to_bytes(&self) -> [u8; ENVELOPE_HEADER_LEN] {
+        let mut bytes = [0u8; ENVELOPE_HEADER_LEN];
+        bytes[1] = self.version;
+        bytes[2] = self.codec as u8;
+        bytes[3..7].copy_from_slice(&self.length.to_be_bytes());
+        bytes[7] = self.format as u8;
+        bytes[8] = self.compression as u8;
+        bytes[9] = self.encryption as u8;
+        bytes
+    }
+
+    /// The header as encryption authenticates it, with the length left zero
+    /// since it is only known once the data is encrypted.
+    fn associated_data(&self) -> [u8; ENVELOPE_HEADER_LEN] {
+        EnvelopeHeader { length: 0, ..*self }.to_bytes()
+    }
+
+    /// Parse the header at the start of `blob`, rejecting versions and
+    /// codecs this crate does not know.
+    pub fn parse(blob: &[u8]) -> Result<Self> {
+        if blob.len() < ENVELOPE_HEADER_LEN {
+            return Err(EigenDaError::Codec(format!(
+                "blob of {} bytes is too short for an envelope header",
+                blob.len()
+            )));
+        }
+        if blob[0] != 0 {
+            return Err(EigenDaError::Codec(
+                "blob does not start with an envelope header".to_string(),
+            ));
+        }
+        if blob[1] != ENVELOPE_VERSION {
+            return Err(EigenDaError::Codec(format!(
+                "unsupported envelope version {}, expected {}",
+                blob[1], ENVELOPE_VERSION
+            )));
+        }
+        let codec = CodecId::try_from(blob[2])?;
+        let length = u32::from_be_bytes(blob[3..7].try_into().expect("slice of 4 bytes."));
+        let format = Format::try_from(blob[7])?;
+        let compression = Compression::try_from(blob[8])?;
+        let encryption = Encryption::try_from(blob[9])?;
+        Ok(EnvelopeHeader::new(
+            codec,
+            length,
+            format,
+            compression,
+            encryption,
+        ))
+    }
+}
+
+/// Wrap raw `data` in an envelope, ready to disperse.
+pub fn encode(data: &[u8]) -> Result<Vec<u8>> {
+    encode_with(data, Format::Raw, Compression::None, None)
+}
+
+/// Wrap `data` serialized in `format` in an envelope, compressing it with
+/// `compression` and, if `encryption_key` is set, encrypting it.
+pub fn encode_with(
+    data: &[u8],
+    format: Format,
+    compression: Compression,
+    encryption_key: Option<&EncryptionKey>,
+) -> Result<Vec<u8>> {
+    let data = compression.compress(data)?;
+    let encryption = match encryption_key {
+        Some(_) => Encryption::ChaCha20Poly1305,
+        None => Encryption::None,
+    };
+    let mut header = EnvelopeHeader::new(
+        CodecId::FieldElementPadding,
+        0,
+        format,
+        compression,
+        encryption,
+    );
+    let data = match encryption_key {
+        Some(key) => key.encrypt(&data, &header.associated_data())?,
+        None => data,
+    };
+    header.length = u32::try_from(data.len()).map_err(|_| {
+        EigenDaError::validation(format!(
+            "data of {} bytes is too long for an envelope",
+            data.len()
+        ))
+    })?;
+
+    let mut blob = header.to_bytes().to_vec();
+    blob.extend(codec::encode(&data));
+    Ok(blob)
+}
+
+/// Unwrap the data of a retrieved envelope, dropping the disperser's
+/// padding.
+pub fn decode(blob: &[u8]) -> Result<Vec<u8>> {
+    decode_with_header(blob).map(|(_, data)| data)
+}
+
+/// Unwrap the data of a retrieved envelope along with its header,
+/// decompressing it to at most [`DEFAULT_MAX_DECOMPRESSED_SIZE`] bytes.
+pub fn decode_with_header(blob: &[u8]) -> Result<(EnvelopeHeader, Vec<u8>)> {
+    decode_with(blob, DEFAULT_MAX_DECOMPRESSED_SIZE, None)
+}
+
+/// Unwrap the data of a retrieved envelope along with its header, failing
+/// if it decompresses to more than `max_decompressed_size` bytes. Encrypted
+/// envelopes are decrypted with the keys of `key_provider`, and fail to
+/// decode without one.
+pub fn decode_with(
+    blob: &[u8],
+    max_decompressed_size: usize,
+    key_provider: Option<&dyn KeyProvider>,
+) -> Result<(EnvelopeHeader, Vec<u8>)> {
+    let header = EnvelopeHeader::parse(blob)?;
+    let body = &blob[ENVELOPE_HEADER_LEN..];
+    let mut data = match header.codec() {
+        CodecId::FieldElementPadding => codec::decode(body)?,
+    };
+
+    let length = header.length() as usize;
+    if data.len() < length {
+        return Err(EigenDaError::Codec(format!(
+            "envelope holds {} bytes, header says {}",
+            data.len(),
+            length
+        )));
+    }
+    data.truncate(length);
+    let data = match (header.encryption(), key_provider) {
+        (Encryption::None, _) => data,
+        (Encryption::ChaCha20Poly1305, Some(keys)) => {
+            encryption::decrypt(&data, keys, &header.associated_data())?
+        }
+        (Encryption::ChaCha20Poly1305, None) => {
+            let (key_id, _) = encryption::split_key_id(&data)?;
+            return Err(EigenDaError::Encryption(format!(
+                "blob is encrypted with key id {:?}, decoding it needs a key provider",
+                key_id
+            )));
+        }
+    };
+    let data = header
+        .compression()
+        .decompress(&data, max_decompressed_size)?;
+    Ok((header, data))
+}
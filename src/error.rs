@@ -1 +1,253 @@
+use prost::Message;
+use regex::Regex;
 
+/// Every way a request to the disperser can fail.
+///
+/// Transports keep the cause of a failure in its own variant, so callers can
+/// match on it rather than on the message.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum EigenDaError {
+    /// The request did not get a reply: the disperser could not be reached,
+    /// the stream closed early or `grpcurl` could not be run.
+    #[error("transport error: {0}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// The disperser replied with a gRPC status other than `OK`.
+    #[error("gRPC error {0}")]
+    Grpc(GrpcStatus),
+
+    /// The disperser replied out of protocol, such as an authenticated
+    /// dispersal stream that does not start with a challenge.
+    #[error("protocol error: {0}")]
+    Protocol(String),
+
+    /// A reply could not be parsed. `body` is the reply as it was received.
+    #[error("failed to parse reply: {source}")]
+    Json {
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+
+    /// Blob data, a request id or a batch header hash was not valid base64.
+    #[error("invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    /// Retrieved data is not in the form the codec expects.
+    #[error("failed to decode blob: {0}")]
+    Codec(String),
+
+    /// A typed record was decoded as another type than it was dispersed as.
+    #[error("expected a record of type {expected:?}, found {found:?}")]
+    TypeMismatch { expected: String, found: String },
+
+    /// The disperser did not connect or reply within the timeout. The
+    /// request was cancelled.
+    #[error("request timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    /// A blob failed to disperse, or is not confirmed yet.
+    #[error("dispersal failed: {0}")]
+    Dispersal(String),
+
+    /// A request was rejected before it was sent.
+    #[error("invalid request: {0}")]
+    Validation(String),
+
+    /// Data could not be encrypted, or a blob could not be decrypted: its
+    /// key is unknown or wrong, or the blob was altered.
+    #[error("encryption error: {0}")]
+    Encryption(String),
+
+    /// A key could not be loaded, or a challenge could not be signed.
+    #[error("signer error: {0}")]
+    Signer(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl EigenDaError {
+    pub fn transport<E: Into<Box<dyn std::error::Error + Send + Sync>>>(err: E) -> Self {
+        EigenDaError::Transport(err.into())
+    }
+
+    pub fn json<B: Into<String>>(source: serde_json::Error, body: B) -> Self {
+        EigenDaError::Json {
+            source,
+            body: body.into(),
+        }
+    }
+
+    pub fn validation<M: Into<String>>(message: M) -> Self {
+        EigenDaError::Validation(message.into())
+    }
+
+    /// Whether the request may succeed if sent again. Transport failures
+    /// and timeouts are retryable, as are the gRPC statuses of
+    /// [`GrpcStatus::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            EigenDaError::Transport(_) | EigenDaError::Timeout(_) => true,
+            EigenDaError::Grpc(status) => status.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// The gRPC status the disperser replied with, if it replied with one.
+    pub fn grpc_status(&self) -> Option<&GrpcStatus> {
+        match self {
+            EigenDaError::Grpc(status) => Some(status),
+            _ => None,
+        }
+    }
+
+    pub fn signer<E: Into<Box<dyn std::error::Error + Send + Sync>>>(err: E) -> Self {
+        EigenDaError::Signer(err.into())
+    }
+}
+
+impl From<tonic::Status> for EigenDaError {
+    fn from(status: tonic::Status) -> Self {
+        EigenDaError::Grpc(status.into())
+    }
+}
+
+impl From<tonic::transport::Error> for EigenDaError {
+    fn from(err: tonic::transport::Error) -> Self {
+        EigenDaError::transport(err)
+    }
+}
+
+/// The gRPC codes of [`GrpcStatus::is_retryable`].
+pub const RETRYABLE_CODES: [tonic::Code; 4] = [
+    tonic::Code::Unavailable,
+    tonic::Code::ResourceExhausted,
+    tonic::Code::DeadlineExceeded,
+    tonic::Code::Aborted,
+];
+
+/// A non-`OK` gRPC status returned by the disperser, from either transport.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrpcStatus {
+    code: tonic::Code,
+    message: String,
+    details: Vec<String>,
+}
+
+impl GrpcStatus {
+    pub fn new(code: tonic::Code, message: String, details: Vec<String>) -> Self {
+        GrpcStatus {
+            code,
+            message,
+            details,
+        }
+    }
+
+    pub fn code(&self) -> tonic::Code {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The status details as JSON, one per detail message. Details whose
+    /// type is unknown are rendered as `{"@type": ..., "value": <base64>}`.
+    pub fn details(&self) -> &[String] {
+        &self.details
+    }
+
+    /// Whether the same request may succeed if sent again: the disperser
+    /// was unavailable, rate limited the caller, or ran out of time.
+    pub fn is_retryable(&self) -> bool {
+        RETRYABLE_CODES.contains(&self.code)
+    }
+
+    /// Parse the status grpcurl prints to stderr when a call fails:
+    ///
+    /// ```text
+    /// ERROR:
+    ///   Code: InvalidArgument
+    ///   Message: blob size must be greater than 0
+    ///   Details:
+    ///   1)  {"@type": "type.googleapis.com/google.rpc.ErrorInfo", ...}
+    /// ```
+    ///
+    /// Returns `None` when stderr holds no status, as when grpcurl could not
+    /// reach the server at all.
+    pub fn from_grpcurl_stderr(stderr: &str) -> Option<Self> {
+        let re = Regex::new(r"(?s)Code:\s*(\w+)\s+Message:\s?(.*?)(?:\s+Details:\s*(.*))?\s*$")
+            .expect("invalid grpcurl status regex.");
+        let captures = re.captures(stderr)?;
+        let code = code_from_name(&captures[1])?;
+        let message = captures[2].trim().to_string();
+        let details = captures
+            .get(3)
+            .map(|details| {
+                Regex::new(r"(?m)^\s*\d+\)\s")
+                    .expect("invalid grpcurl details regex.")
+                    .split(details.as_str())
+                    .map(|detail| {
+                        detail
+                            .lines()
+                            .map(|line| line.trim())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .filter(|detail| !detail.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(GrpcStatus::new(code, message, details))
+    }
+}
+
+impl std::fmt::Display for GrpcStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl From<tonic::Status> for GrpcStatus {
+    fn from(status: tonic::Status) -> Self {
+        let details = match crate::grpc::rpc::Status::decode(status.details()) {
+            Ok(rpc_status) if !status.details().is_empty() => rpc_status
+                .details
+                .iter()
+                .map(|detail| {
+                    serde_json::json!({
+                        "@type": detail.type_url,
+                        "value": base64::encode(&detail.value),
+                    })
+                    .to_string()
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        GrpcStatus::new(status.code(), status.message().to_string(), details)
+    }
+}
+
+/// The `tonic::Code` of a status code name as grpc-go prints it.
+fn code_from_name(name: &str) -> Option<tonic::Code> {
+    let code = match name {
+        "OK" => tonic::Code::Ok,
+        "Canceled" | "Cancelled" => tonic::Code::Cancelled,
+        "Unknown" => tonic::Code::Unknown,
+        "InvalidArgument" => tonic::Code::InvalidArgument,
+        "DeadlineExceeded" => tonic::Code::DeadlineExceeded,
+        "NotFound" => tonic::Code::NotFound,
+        "AlreadyExists" => tonic::Code::AlreadyExists,
+        "PermissionDenied" => tonic::Code::PermissionDenied,
+        "ResourceExhausted" => tonic::Code::ResourceExhausted,
+        "FailedPrecondition" => tonic::Code::FailedPrecondition,
+        "Aborted" => tonic::Code::Aborted,
+        "OutOfRange" => tonic::Code::OutOfRange,
+        "Unimplemented" => tonic::Code::Unimplemented,
+        "Internal" => tonic::Code::Internal,
+        "Unavailable" => tonic::Code::Unavailable,
+        "DataLoss" => tonic::Code::DataLoss,
+        "Unauthenticated" => tonic::Code::Unauthenticated,
+        _ => return None,
+    };
+    Some(code)
+}
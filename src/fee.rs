@@ -3,8 +3,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct BlobFee(String);
 
-impl ToString for BlobFee {
-    fn to_string(&self) -> String {
-        self.0.clone()
+impl std::fmt::Display for BlobFee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
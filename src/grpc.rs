@@ -0,0 +1,156 @@
+//! Rust types generated from the vendored EigenDA protos, and their
+//! rendering into the JSON form grpcurl prints so replies received over the
+//! native transport parse into the same models as grpcurl output.
+use serde_json::{json, Value};
+
+pub mod common {
+    tonic::include_proto!("common");
+}
+
+pub mod disperser {
+    tonic::include_proto!("disperser");
+}
+
+/// The `google.rpc.Status` a server may attach to a failed call as the
+/// `grpc-status-details-bin` trailer.
+pub mod rpc {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Status {
+        #[prost(int32, tag = "1")]
+        pub code: i32,
+        #[prost(string, tag = "2")]
+        pub message: String,
+        #[prost(message, repeated, tag = "3")]
+        pub details: Vec<Any>,
+    }
+
+    /// `google.protobuf.Any`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Any {
+        #[prost(string, tag = "1")]
+        pub type_url: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub value: Vec<u8>,
+    }
+}
+
+use disperser::{
+    BatchHeader, BatchMetadata, BlobHeader, BlobInfo, BlobQuorumParam, BlobStatusReply,
+    BlobVerificationProof, DisperseBlobReply, RetrieveBlobReply,
+};
+
+/// The proto enum name of a `disperser.BlobStatus` value.
+pub fn blob_status_name(status: i32) -> &'static str {
+    match disperser::BlobStatus::from_i32(status) {
+        Some(disperser::BlobStatus::Processing) => "PROCESSING",
+        Some(disperser::BlobStatus::Confirmed) => "CONFIRMED",
+        Some(disperser::BlobStatus::Failed) => "FAILED",
+        Some(disperser::BlobStatus::Finalized) => "FINALIZED",
+        Some(disperser::BlobStatus::InsufficientSignatures) => "INSUFFICIENT_SIGNATURES",
+        Some(disperser::BlobStatus::Dispersing) => "DISPERSING",
+        Some(disperser::BlobStatus::Unknown) | None => "UNKNOWN",
+    }
+}
+
+impl DisperseBlobReply {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "result": blob_status_name(self.result),
+            "requestId": base64::encode(&self.request_id),
+        })
+    }
+}
+
+impl BlobStatusReply {
+    pub fn to_json(&self) -> Value {
+        let mut value = json!({ "status": blob_status_name(self.status) });
+        if let Some(info) = &self.info {
+            value["info"] = info.to_json();
+        }
+        value
+    }
+}
+
+impl RetrieveBlobReply {
+    pub fn to_json(&self) -> Value {
+        json!({ "data": base64::encode(&self.data) })
+    }
+}
+
+impl BlobInfo {
+    pub fn to_json(&self) -> Value {
+        let mut value = json!({});
+        if let Some(header) = &self.blob_header {
+            value["blobHeader"] = header.to_json();
+        }
+        if let Some(proof) = &self.blob_verification_proof {
+            value["blobVerificationProof"] = proof.to_json();
+        }
+        value
+    }
+}
+
+impl BlobHeader {
+    pub fn to_json(&self) -> Value {
+        let commitment = self.commitment.clone().unwrap_or_default();
+        json!({
+            "commitment": {
+                "x": base64::encode(&commitment.x),
+                "y": base64::encode(&commitment.y),
+            },
+            "dataLength": self.data_length,
+            "blobQuorumParams": self
+                .blob_quorum_params
+                .iter()
+                .map(BlobQuorumParam::to_json)
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl BlobQuorumParam {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "quorumNumber": self.quorum_number,
+            "adversaryThresholdPercentage": self.adversary_threshold_percentage,
+            "quorumThresholdPercentage": self.quorum_threshold_percentage,
+            "quantizationParam": self.quantization_param,
+            "encodedLength": self.encoded_length.to_string(),
+        })
+    }
+}
+
+impl BlobVerificationProof {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "batchId": self.batch_id,
+            "blobIndex": self.blob_index,
+            "batchMetadata": self.batch_metadata.clone().unwrap_or_default().to_json(),
+            "inclusionProof": base64::encode(&self.inclusion_proof),
+            "quorumIndexes": base64::encode(&self.quorum_indexes),
+        })
+    }
+}
+
+impl BatchMetadata {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "batchHeader": self.batch_header.clone().unwrap_or_default().to_json(),
+            "signatoryRecordHash": base64::encode(&self.signatory_record_hash),
+            "fee": base64::encode(&self.fee),
+            "confirmationBlockNumber": self.confirmation_block_number,
+            "batchHeaderHash": base64::encode(&self.batch_header_hash),
+        })
+    }
+}
+
+impl BatchHeader {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "batchRoot": base64::encode(&self.batch_root),
+            "quorumNumbers": base64::encode(&self.quorum_numbers),
+            "quorumSignedPercentages": base64::encode(&self.quorum_signed_percentages),
+            "referenceBlockNumber": self.reference_block_number,
+        })
+    }
+}
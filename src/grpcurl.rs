@@ -0,0 +1,320 @@
+use crate::batch::BatchHeaderHash;
+use crate::blob::EncodedBlob;
+use crate::client::{
+    EigenDaGrpcClient, DEFAULT_EIGENDA_SERVER_ADDRESS, EIGENDA_COMMON_PROTO_FILENAME,
+    EIGENDA_DISPERSER_PROTO_FILENAME, EIGENDA_PROTO_PATH,
+};
+use crate::error::{EigenDaError, GrpcStatus};
+use crate::grpcurl_command;
+use crate::payload::EigenDaBlobPayload;
+use crate::response::BlobResponse;
+use crate::result::Result;
+use crate::status::BlobStatus;
+use crate::timeout::Timeouts;
+use crate::transport::{AsyncDisperserTransport, DisperserTransport};
+use async_trait::async_trait;
+use derive_builder::Builder;
+use regex::Regex;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Sends disperser requests by forking `grpcurl`, which reads the EigenDA
+/// protos from `proto_path` and `disperser_path`.
+#[derive(Builder, Clone, Debug)]
+pub struct GrpcurlTransport {
+    #[builder(default)]
+    grpcurl_bin_path: Option<String>,
+    proto_path: String,
+    disperser_path: String,
+    server_address: String,
+}
+
+impl Default for GrpcurlTransport {
+    fn default() -> Self {
+        let mut eigenda_proto_path = std::env::current_dir()
+            .expect("failed to get current directory for checking eigenda api path.");
+        eigenda_proto_path.push(EIGENDA_PROTO_PATH);
+
+        let mut common_path = eigenda_proto_path.clone();
+        common_path.push("common");
+        common_path.push(EIGENDA_COMMON_PROTO_FILENAME);
+        write_proto(
+            &common_path,
+            include_bytes!("../eigenda/api/proto/common/common.proto"),
+        );
+
+        let mut disperser_path = eigenda_proto_path.clone();
+        disperser_path.push("disperser");
+        disperser_path.push(EIGENDA_DISPERSER_PROTO_FILENAME);
+        write_proto(
+            &disperser_path,
+            include_bytes!("../eigenda/api/proto/disperser/disperser.proto"),
+        );
+
+        GrpcurlTransportBuilder::default()
+            .grpcurl_bin_path(None)
+            .proto_path(
+                eigenda_proto_path
+                    .to_str()
+                    .expect("failed to convert eigenda proto path to &str")
+                    .to_string(),
+            )
+            .disperser_path(
+                disperser_path
+                    .to_str()
+                    .expect("failed to convert eigenda disperser proto path to &str")
+                    .to_string(),
+            )
+            .server_address(DEFAULT_EIGENDA_SERVER_ADDRESS.to_string())
+            .build()
+            .expect("failed to build grpcurl transport.")
+    }
+}
+
+fn write_proto(path: &std::path::Path, contents: &[u8]) {
+    if let Some(dir) = path.parent() {
+        if !dir.exists() {
+            std::fs::create_dir_all(dir).expect("failed to create eigenda proto path.");
+        }
+    }
+    std::fs::write(path, contents).expect("failed to write eigenda proto api to file.");
+}
+
+impl GrpcurlTransport {
+    /// Update the EigenDA server address with some URL address other than [`DEFAULT_EIGENDA_SERVER_ADDRESS`].
+    pub fn update_server_address(&mut self, address: String) {
+        self.server_address = address;
+    }
+
+    pub fn update_grpcurl_bin_path(&mut self, path: Option<String>) {
+        self.grpcurl_bin_path = path;
+    }
+}
+
+impl DisperserTransport for GrpcurlTransport {
+    fn with_server_address(&self, address: &str) -> Result<Arc<dyn DisperserTransport>> {
+        let mut transport = self.clone();
+        transport.update_server_address(address.to_string());
+        Ok(Arc::new(transport))
+    }
+
+    fn with_grpcurl_bin_path(&self, path: Option<String>) -> Result<Arc<dyn DisperserTransport>> {
+        let mut transport = self.clone();
+        transport.update_grpcurl_bin_path(path);
+        Ok(Arc::new(transport))
+    }
+
+    fn disperse_blob(
+        &self,
+        payload: &EigenDaBlobPayload,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        let payload: String = payload.into();
+
+        let connect_timeout = connect_timeout_arg(timeouts);
+        let output = grpcurl_command!(
+            &self.grpcurl_bin_path.as_ref(),
+            timeouts.rpc(),
+            "-connect-timeout",
+            &connect_timeout,
+            "-emit-defaults",
+            "-import-path",
+            &self.proto_path,
+            "-proto",
+            &self.disperser_path,
+            "-d",
+            &payload,
+            &self.server_address,
+            EigenDaGrpcClient::DISPERSE_BLOB
+        )?;
+
+        BlobResponse::try_from(command_output(output)?)
+    }
+
+    fn get_blob_status(&self, request_id: &str, timeouts: &Timeouts) -> Result<BlobStatus> {
+        let payload = serde_json::json!({
+            "request_id": request_id
+        });
+
+        let connect_timeout = connect_timeout_arg(timeouts);
+        let output = grpcurl_command!(
+            &self.grpcurl_bin_path.as_ref(),
+            timeouts.rpc(),
+            "-connect-timeout",
+            &connect_timeout,
+            "-emit-defaults",
+            "-import-path",
+            &self.proto_path,
+            "-proto",
+            &self.disperser_path,
+            "-d",
+            &payload.to_string(),
+            &self.server_address,
+            EigenDaGrpcClient::GET_BLOB_STATUS
+        )?;
+
+        let response = command_output(output)?;
+        let re = Regex::new(r"(\\n|\\t|\n\t|\s\s+)").unwrap();
+        let clean_response = re.replace_all(&response, " ").to_string();
+        BlobStatus::from_str(&clean_response).map_err(|err| {
+            log::error!("{}", err);
+            EigenDaError::json(err, response)
+        })
+    }
+
+    fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        timeouts: &Timeouts,
+    ) -> Result<EncodedBlob> {
+        let payload = serde_json::json!({
+            "batch_header_hash": batch_header_hash.to_base64(),
+            "blob_index": blob_index.to_string()
+        });
+
+        let connect_timeout = connect_timeout_arg(timeouts);
+        let output = grpcurl_command!(
+            &self.grpcurl_bin_path.as_ref(),
+            timeouts.rpc(),
+            "-connect-timeout",
+            &connect_timeout,
+            "-emit-defaults",
+            "-import-path",
+            &self.proto_path,
+            "-proto",
+            &self.disperser_path,
+            "-d",
+            &payload.to_string(),
+            &self.server_address,
+            EigenDaGrpcClient::RETRIEVE_BLOB
+        )?;
+
+        let response = command_output(output)?;
+        EncodedBlob::from_str(&response).map_err(|err| EigenDaError::json(err, response))
+    }
+}
+
+/// grpcurl's `-connect-timeout`, its own default of 10 seconds if unset.
+fn connect_timeout_arg(timeouts: &Timeouts) -> String {
+    timeouts
+        .connect()
+        .unwrap_or(Duration::from_secs(10))
+        .as_secs_f64()
+        .to_string()
+}
+
+/// Run `command` to completion, or kill it once `timeout` passes and return
+/// `None`. Used by [`grpcurl_command!`].
+#[doc(hidden)]
+pub fn output_with_timeout(
+    mut command: Command,
+    timeout: Option<Duration>,
+) -> std::io::Result<Option<Output>> {
+    let Some(timeout) = timeout else {
+        return command.output().map(Some);
+    };
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain the pipes while waiting, so a large reply cannot block grpcurl.
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            // The child may exit between the wait and the kill.
+            child.kill().ok();
+            child.wait()?;
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf).ok();
+        }
+        buf
+    })
+}
+
+/// The stdout of a grpcurl call. A failed call is a gRPC error when stderr
+/// holds the status the server replied with, and a transport error otherwise.
+fn command_output(output: std::process::Output) -> Result<String> {
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(EigenDaError::transport)
+    } else {
+        let error_message = String::from_utf8_lossy(&output.stderr).into_owned();
+        match GrpcStatus::from_grpcurl_stderr(&error_message) {
+            Some(status) => Err(EigenDaError::Grpc(status)),
+            None => Err(EigenDaError::transport(error_message)),
+        }
+    }
+}
+
+/// Runs each grpcurl request on tokio's blocking pool, so awaiting it does
+/// not stall the async runtime.
+#[async_trait]
+impl AsyncDisperserTransport for GrpcurlTransport {
+    async fn disperse_blob(
+        &self,
+        payload: &EigenDaBlobPayload,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        let transport = self.clone();
+        let payload = payload.clone();
+        let timeouts = *timeouts;
+        tokio::task::spawn_blocking(move || {
+            DisperserTransport::disperse_blob(&transport, &payload, &timeouts)
+        })
+        .await
+        .map_err(EigenDaError::transport)?
+    }
+
+    async fn get_blob_status(&self, request_id: &str, timeouts: &Timeouts) -> Result<BlobStatus> {
+        let transport = self.clone();
+        let request_id = request_id.to_string();
+        let timeouts = *timeouts;
+        tokio::task::spawn_blocking(move || {
+            DisperserTransport::get_blob_status(&transport, &request_id, &timeouts)
+        })
+        .await
+        .map_err(EigenDaError::transport)?
+    }
+
+    async fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        timeouts: &Timeouts,
+    ) -> Result<EncodedBlob> {
+        let transport = self.clone();
+        let batch_header_hash = batch_header_hash.clone();
+        let timeouts = *timeouts;
+        tokio::task::spawn_blocking(move || {
+            DisperserTransport::retrieve_blob(&transport, &batch_header_hash, blob_index, &timeouts)
+        })
+        .await
+        .map_err(EigenDaError::transport)?
+    }
+}
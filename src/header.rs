@@ -2,11 +2,13 @@ use crate::commitment::BlobCommitment;
 use crate::quorum::BlobQuorumParams;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BlobHeader {
     commitment: BlobCommitment,
-    data_length: usize,
+    /// The length of the blob in 32-byte field elements.
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    data_length: u32,
     blob_quorum_params: Vec<BlobQuorumParams>,
 }
 
@@ -15,7 +17,7 @@ impl BlobHeader {
         &self.commitment
     }
 
-    pub fn data_length(&self) -> usize {
+    pub fn data_length(&self) -> u32 {
         self.data_length
     }
 
@@ -23,13 +25,3 @@ impl BlobHeader {
         &self.blob_quorum_params
     }
 }
-
-impl Default for BlobHeader {
-    fn default() -> Self {
-        BlobHeader {
-            commitment: Default::default(),
-            data_length: Default::default(),
-            blob_quorum_params: Default::default(),
-        }
-    }
-}
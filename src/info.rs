@@ -2,7 +2,7 @@ use crate::header::BlobHeader;
 use crate::proof::BlobVerificationProof;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BlobInfo {
     blob_header: Option<BlobHeader>,
@@ -25,13 +25,8 @@ impl BlobInfo {
 
         None
     }
-}
 
-impl Default for BlobInfo {
-    fn default() -> Self {
-        BlobInfo {
-            blob_header: None,
-            blob_verification_proof: None,
-        }
+    pub fn into_parts(self) -> (Option<BlobHeader>, Option<BlobVerificationProof>) {
+        (self.blob_header, self.blob_verification_proof)
     }
 }
@@ -2,27 +2,53 @@ use crate::cache::LruCache;
 use crate::response::BlobResponse;
 use ritelinked::LinkedHashSet;
 
+pub mod async_client;
 pub mod batch;
 pub mod blob;
+pub mod bytes;
 pub mod cache;
+pub mod chunked;
 pub mod client;
+pub mod codec;
 pub mod commitment;
+pub mod compression;
+mod encoding;
+pub mod encryption;
+pub mod envelope;
 pub mod error;
 pub mod fee;
+pub mod grpc;
+#[cfg(feature = "grpcurl")]
+pub mod grpcurl;
 pub mod header;
 pub mod info;
 pub mod macros;
 pub mod meta;
 pub mod methods;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod native;
+pub mod number;
+pub mod packer;
 pub mod payload;
 pub mod proof;
 pub mod quorum;
 pub mod record;
+pub mod reference;
+pub mod reply;
 pub mod response;
 pub mod result;
+pub mod retry;
+pub mod signer;
+pub mod state;
 pub mod status;
+pub mod timeout;
+pub mod transport;
+pub mod typed;
 
+pub use async_client::*;
 pub use client::*;
+pub use error::EigenDaError;
 
 impl LruCache for LinkedHashSet<BlobResponse> {
     type Value = BlobResponse;
@@ -32,43 +58,1168 @@ impl LruCache for LinkedHashSet<BlobResponse> {
     }
 
     fn get(&self, key: &Self::Item) -> Option<&Self::Value> {
-        self.get(&key)
+        self.get(key)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::async_client::{AsyncEigenDaClient, AsyncEigenDaClientBuilder};
+    use crate::batch::{BatchHeader, BatchHeaderHash};
     use crate::blob::{DecodedBlob, EncodedBlob};
-    use crate::client::EigenDaGrpcClient;
-    use crate::status::BlobResult;
-    use std::thread;
+    use crate::chunked::{self, ChunkedOptionsBuilder, Manifest, ManifestChunk};
+    use crate::client::{EigenDaGrpcClient, EigenDaGrpcClientBuilder};
+    use crate::codec;
+    use crate::commitment::{BlobCommitment, FieldElement, G1Point};
+    use crate::compression::Compression;
+    use crate::encryption::{self, EncryptionKey, KeyRing};
+    use crate::envelope;
+    use crate::error::{EigenDaError, GrpcStatus};
+    use crate::methods::EigenDaGrpcMethod;
+    use crate::mock::{MockDisperser, MockServer};
+    use crate::native::TonicTransport;
+    use crate::packer::{self, BlobPacker};
+    use crate::payload::{DisperseOptionsBuilder, EigenDaBlobPayload};
+    use crate::proof::{BlobInclusionProof, BlobVerificationProof};
+    use crate::quorum::BlobQuorumParams;
+    use crate::reference::BlobReference;
+    use crate::response::BlobResponse;
+    use crate::result::Result;
+    use crate::retry::{RetryPolicy, RetryPolicyBuilder};
+    use crate::signer::{LocalSigner, Signer};
+    use crate::state::BlobState;
+    use crate::status::{BlobResult, BlobStatus};
+    use crate::timeout::Timeouts;
+    use crate::transport::DisperserTransport;
+    use crate::typed::{self, Format};
+    use sha3::{Digest, Keccak256};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
     use std::time::Duration;
 
     #[test]
-    #[ignore = "encountered an error to convert a 32-bytes into a valid field element, please use the correct format where every 32bytes(big-endian) is less than 21888242871839275222246405745257275088548364400416034343698204186575808495617"]
+    #[ignore = "disperses to the public holesky disperser"]
     fn test_disperse_get_status_and_retrieve_blob() {
         let client = create_client();
-        let arbitrary_data = base64::encode("ArbitraryData");
 
-        let blob_response = client.disperse_blob(arbitrary_data).unwrap();
-        let mut blob_status = client.get_blob_status(&blob_response.request_id()).unwrap();
-        while blob_status.status() != &BlobResult::Confirmed {
-            thread::sleep(Duration::from_secs(30));
-            blob_status = client.get_blob_status(&blob_response.request_id()).unwrap();
+        let blob_response = client.disperse_data(b"ArbitraryData").unwrap();
+        let blob = client
+            .wait_for_confirmation(
+                &blob_response.request_id(),
+                Duration::from_secs(30),
+                Some(Duration::from_secs(30 * 60)),
+            )
+            .unwrap();
+
+        let data = client
+            .retrieve_data(blob.batch_header_hash(), blob.blob_index())
+            .unwrap();
+        assert_eq!(data, b"ArbitraryData");
+    }
+
+    fn create_client() -> EigenDaGrpcClient {
+        EigenDaGrpcClient::default()
+    }
+
+    /// A client of a new mock disperser, which stops when the returned
+    /// server is dropped.
+    fn mock_client() -> (MockServer, EigenDaGrpcClient) {
+        let server = MockDisperser::new().start().unwrap();
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(TonicTransport::new(server.uri()))
+            .build()
+            .unwrap();
+        (server, client)
+    }
+
+    fn mock_async_client() -> (MockServer, AsyncEigenDaClient) {
+        let server = MockDisperser::new().start().unwrap();
+        let client = AsyncEigenDaClientBuilder::default()
+            .transport(TonicTransport::new(server.uri()))
+            .build()
+            .unwrap();
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_mock_disperse_get_status_and_retrieve_blob() {
+        let (_server, client) = mock_async_client();
+        let data = vec![0, 1, 2, 3];
+
+        let blob_response = client.disperse_blob(base64::encode(&data)).await.unwrap();
+        assert_eq!(blob_response.result(), &BlobResult::Processing);
+
+        let confirmed = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .await
+            .unwrap();
+        assert_eq!(confirmed.status(), &BlobResult::Confirmed);
+
+        let blob = client
+            .retrieve_blob(confirmed.batch_header_hash(), confirmed.blob_index())
+            .await
+            .unwrap();
+
+        let decoded_blob = base64::decode(blob.data()).unwrap();
+        assert_eq!(decoded_blob.len(), 32);
+        assert_eq!(decoded_blob[..data.len()], data);
+
+        let blob_status = client
+            .get_blob_status(&blob_response.request_id())
+            .await
+            .unwrap();
+        assert_eq!(blob_status.status(), &BlobResult::Finalized);
+    }
+
+    #[test]
+    fn test_mock_rejects_invalid_field_elements() {
+        let (_server, client) = mock_client();
+
+        let err = client
+            .disperse_blob(base64::encode("ArbitraryData"))
+            .unwrap_err();
+        let status = err.grpc_status().expect("expected a gRPC status");
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("valid field element"));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_mock_authenticated_dispersal() {
+        let server = MockDisperser::new().start().unwrap();
+        let signer = LocalSigner::from_hex(
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .unwrap();
+        assert!(signer.account_id().starts_with("0x04"));
+
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(TonicTransport::new(server.uri()))
+            .signer(signer)
+            .build()
+            .unwrap();
+        let blob_response = client.disperse_blob(base64::encode([0, 1, 2, 3])).unwrap();
+        assert_eq!(blob_response.result(), &BlobResult::Processing);
+
+        let blob_status = client.get_blob_status(&blob_response.request_id()).unwrap();
+        assert_eq!(blob_status.status(), &BlobResult::Processing);
+    }
+
+    #[test]
+    fn test_mock_disperse_to_custom_quorums() {
+        let (_server, client) = mock_client();
+        let options = DisperseOptionsBuilder::default()
+            .custom_quorum_numbers(vec![1, 2])
+            .account_id("0x1234")
+            .build()
+            .unwrap();
+        let record = Record {
+            id: 1,
+            name: "rollup".to_string(),
+        };
+        let mut packer = BlobPacker::default();
+        packer.push(b"message").unwrap();
+
+        let responses = [
+            client.disperse_blob_with_options(base64::encode([0, 1, 2, 3]), options.clone()),
+            client.disperse_typed_with_options(&record, Format::Json, "record.v1", options.clone()),
+            client.disperse_packed_with_options(&packer, options),
+        ];
+        for blob_response in responses {
+            let blob = client
+                .wait_for_confirmation(&blob_response.unwrap().request_id(), Duration::ZERO, None)
+                .unwrap();
+            assert_eq!(blob.blob_quorum_params().len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_disperse_options_are_validated_before_sending() {
+        let client = create_client();
+        for quorums in [vec![2, 3, 2], vec![255]] {
+            let options = DisperseOptionsBuilder::default()
+                .custom_quorum_numbers(quorums)
+                .build()
+                .unwrap();
+            let err = client
+                .disperse_blob_with_options(base64::encode([0]), options)
+                .unwrap_err();
+            assert!(matches!(err, EigenDaError::Validation(_)));
+        }
+    }
+
+    #[test]
+    fn test_parse_grpcurl_status() {
+        let status = GrpcStatus::from_grpcurl_stderr(
+            "ERROR:\n  Code: InvalidArgument\n  Message: blob size must be greater than 0\n",
+        )
+        .unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.message(), "blob size must be greater than 0");
+        assert!(status.details().is_empty());
+        assert!(!status.is_retryable());
+
+        let status = GrpcStatus::from_grpcurl_stderr(
+            "ERROR: Code: ResourceExhausted Message: request ratelimited",
+        )
+        .unwrap();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert_eq!(status.message(), "request ratelimited");
+        assert!(status.is_retryable());
+
+        let status = GrpcStatus::from_grpcurl_stderr(
+            "ERROR:\n  Code: Unavailable\n  Message: try again\n  Details:\n  1)\t{\n    \t  \"@type\": \"type.googleapis.com/google.rpc.RetryInfo\"\n    \t}\n  2)\t{\"@type\": \"type.googleapis.com/google.rpc.ErrorInfo\"}\n",
+        )
+        .unwrap();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(status.message(), "try again");
+        assert_eq!(
+            status.details(),
+            [
+                r#"{ "@type": "type.googleapis.com/google.rpc.RetryInfo" }"#,
+                r#"{"@type": "type.googleapis.com/google.rpc.ErrorInfo"}"#,
+            ]
+        );
+
+        assert!(GrpcStatus::from_grpcurl_stderr(
+            "Failed to dial target host \"localhost:1\": connection refused"
+        )
+        .is_none());
+    }
+
+    /// Fails every request with `code` until `failures` requests have failed.
+    #[derive(Debug)]
+    struct FlakyTransport {
+        code: tonic::Code,
+        failures: u32,
+        calls: Arc<AtomicU32>,
+    }
+
+    impl FlakyTransport {
+        fn call<T: Default>(&self) -> Result<T> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err(tonic::Status::new(self.code, "flaky").into())
+            } else {
+                Ok(T::default())
+            }
+        }
+    }
+
+    impl DisperserTransport for FlakyTransport {
+        fn disperse_blob(
+            &self,
+            _payload: &EigenDaBlobPayload,
+            _timeouts: &Timeouts,
+        ) -> Result<BlobResponse> {
+            self.call()
+        }
+
+        fn get_blob_status(&self, _request_id: &str, _timeouts: &Timeouts) -> Result<BlobStatus> {
+            self.call()
+        }
+
+        fn retrieve_blob(
+            &self,
+            _batch_header_hash: &BatchHeaderHash,
+            _blob_index: u32,
+            _timeouts: &Timeouts,
+        ) -> Result<EncodedBlob> {
+            self.call().map(|()| EncodedBlob::new(String::new()))
+        }
+    }
+
+    fn flaky_client(code: tonic::Code, failures: u32) -> (EigenDaGrpcClient, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(FlakyTransport {
+                code,
+                failures,
+                calls: calls.clone(),
+            })
+            .retry_policy(
+                RetryPolicyBuilder::default()
+                    .max_attempts(3)
+                    .initial_backoff(Duration::from_millis(1))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        (client, calls)
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let (client, calls) = flaky_client(tonic::Code::Unavailable, 2);
+        client.get_blob_status("request").unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let (client, calls) = flaky_client(tonic::Code::Unavailable, 3);
+        let err = client.get_blob_status("request").unwrap_err();
+        assert_eq!(err.grpc_status().unwrap().code(), tonic::Code::Unavailable);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let (client, calls) = flaky_client(tonic::Code::InvalidArgument, 1);
+        client
+            .retrieve_blob(&BatchHeaderHash::default(), 0)
+            .unwrap_err();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let (client, calls) = flaky_client(tonic::Code::Unavailable, 1);
+        client.disperse_blob(base64::encode([0])).unwrap_err();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retry_policy_follows_is_retryable() {
+        let policy = RetryPolicy::default();
+        let retries = |err: &EigenDaError| {
+            let retried = policy.should_retry(EigenDaGrpcMethod::GetBlobStatus, 1, err);
+            assert_eq!(retried, err.is_retryable());
+            retried
+        };
+        assert!(retries(&EigenDaError::transport("connection reset")));
+        assert!(retries(&EigenDaError::Timeout(Duration::from_secs(1))));
+        assert!(retries(&tonic::Status::unavailable("busy").into()));
+        assert!(!retries(&tonic::Status::invalid_argument("empty").into()));
+        assert!(!retries(&EigenDaError::Protocol(
+            "expected a challenge from the disperser".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_rpc_timeout_cancels_request() {
+        let server = MockDisperser::new()
+            .reply_delay(Duration::from_secs(5))
+            .start()
+            .unwrap();
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(TonicTransport::new(server.uri()))
+            .connect_timeout(Duration::from_secs(5))
+            .rpc_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let err = client.disperse_blob(base64::encode([0])).unwrap_err();
+        assert!(
+            matches!(err, EigenDaError::Timeout(timeout) if timeout == Duration::from_millis(100))
+        );
+
+        let client = client.with_timeouts(Timeouts::new().with_rpc(Duration::from_millis(50)));
+        assert_eq!(client.timeouts().connect(), Some(Duration::from_secs(5)));
+        let err = client.get_blob_status("cmVxdWVzdA==").unwrap_err();
+        assert!(
+            matches!(err, EigenDaError::Timeout(timeout) if timeout == Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "grpcurl")]
+    fn test_grpcurl_is_killed_at_the_deadline() {
+        let mut command = std::process::Command::new("sleep");
+        command.arg("5");
+        let started = std::time::Instant::now();
+        let output =
+            crate::grpcurl::output_with_timeout(command, Some(Duration::from_millis(100))).unwrap();
+        assert!(output.is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_codec_round_trip() {
+        for len in [0, 1, 30, 31, 32, 62, 100] {
+            let data: Vec<u8> = (0..len).map(|i| 0xff - i as u8).collect();
+            let encoded = codec::encode(&data);
+            assert_eq!(encoded.len(), codec::encoded_len(len));
+            assert!(codec::is_field_element_safe(&encoded));
+            assert_eq!(codec::decode(&encoded).unwrap(), data);
         }
+        assert!(!codec::is_field_element_safe(&[0xff; 32]));
+        assert!(matches!(
+            codec::decode(&[0xff; 32]),
+            Err(EigenDaError::Codec(_))
+        ));
+    }
+
+    #[test]
+    fn test_mock_disperse_and_retrieve_data() {
+        let (_server, client) = mock_client();
+        let data = [0xff; 40];
+
+        let blob_response = client.disperse_data(&data).unwrap();
+        let confirmed = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+
+        let retrieved = client
+            .retrieve_data(confirmed.batch_header_hash(), confirmed.blob_index())
+            .unwrap();
+        assert_eq!(retrieved, data);
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        for len in [0, 1, 31, 100] {
+            let data = vec![0xab; len];
+            let mut blob = envelope::encode(&data).unwrap();
+            // The disperser pads blobs to whole field elements.
+            blob.resize(blob.len().div_ceil(32) * 32, 0);
+            let decoded = DecodedBlob::from_encoded(EncodedBlob::new(base64::encode(&blob)));
+            assert_eq!(decoded.unwrap().data(), data);
+        }
+
+        let mut blob = envelope::encode(b"data").unwrap();
+        blob[1] = envelope::ENVELOPE_VERSION + 1;
+        let err = envelope::decode(&blob).unwrap_err();
+        assert!(matches!(err, EigenDaError::Codec(_)));
+        assert!(err.to_string().contains("unsupported envelope version"));
+    }
+
+    #[test]
+    fn test_decode_raw_blob() {
+        let blob = codec::encode(b"data dispersed without an envelope");
+        let encoded = EncodedBlob::new(base64::encode(&blob));
+        assert!(DecodedBlob::from_encoded(encoded.clone()).is_err());
+
+        let decoded = DecodedBlob::from_raw(encoded).unwrap();
+        assert_eq!(decoded.format(), Format::Raw);
+        assert_eq!(decoded.data(), blob);
+        assert!(codec::decode(&decoded.data())
+            .unwrap()
+            .starts_with(b"data dispersed without an envelope"));
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Record {
+        id: u64,
+        name: String,
+    }
+
+    #[test]
+    fn test_typed_records() {
+        let record = Record {
+            id: 7,
+            name: "batch".to_string(),
+        };
+        for format in [Format::Json, Format::Bincode] {
+            let blob = typed::serialize(&record, format, "record.v1").unwrap();
+            let blob = envelope::encode_with(&blob, format, Compression::None, None).unwrap();
+            let decoded =
+                DecodedBlob::from_encoded(EncodedBlob::new(base64::encode(blob))).unwrap();
+            assert_eq!(decoded.format(), format);
+            assert_eq!(decoded.type_tag(), Some("record.v1"));
+            assert_eq!(decoded.deserialize::<Record>("record.v1").unwrap(), record);
+            assert!(matches!(
+                decoded.deserialize::<Record>("record.v2"),
+                Err(EigenDaError::TypeMismatch { .. })
+            ));
+        }
+
+        let blob = envelope::encode(b"text").unwrap();
+        let decoded = DecodedBlob::from_encoded(EncodedBlob::new(base64::encode(blob))).unwrap();
+        assert_eq!(decoded.data_to_string().unwrap(), "text");
+        assert!(decoded.type_tag().is_none());
+    }
+
+    #[test]
+    fn test_mock_disperse_and_retrieve_typed() {
+        let (_server, client) = mock_client();
+        let record = Record {
+            id: 1,
+            name: "rollup".to_string(),
+        };
+
+        let blob_response = client
+            .disperse_typed(&record, Format::Bincode, "record.v1")
+            .unwrap();
+        let confirmed = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+
+        let retrieved: Record = client
+            .retrieve_typed(
+                confirmed.batch_header_hash(),
+                confirmed.blob_index(),
+                "record.v1",
+            )
+            .unwrap();
+        assert_eq!(retrieved, record);
+    }
+
+    #[test]
+    fn test_compressed_envelope_round_trip() {
+        let data = b"compressible ".repeat(100);
+        let algorithms = [
+            #[cfg(feature = "zstd")]
+            Compression::Zstd,
+            #[cfg(feature = "gzip")]
+            Compression::Gzip,
+            #[cfg(feature = "lz4")]
+            Compression::Lz4,
+            Compression::None,
+        ];
+        for compression in algorithms {
+            let blob = envelope::encode_with(&data, Format::Raw, compression, None).unwrap();
+            let (header, decoded) = envelope::decode_with_header(&blob).unwrap();
+            assert_eq!(header.compression(), compression);
+            assert_eq!(decoded, data);
+            if compression != Compression::None {
+                assert!(blob.len() < data.len());
+                let err = envelope::decode_with(&blob, data.len() - 1, None).unwrap_err();
+                assert!(matches!(err, EigenDaError::Codec(_)));
+            }
+        }
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_compression_not_compiled_in() {
+        let err = envelope::encode_with(b"data", Format::Raw, Compression::Zstd, None).unwrap_err();
+        assert!(err.to_string().contains("zstd"));
+
+        let mut blob = envelope::encode(b"data").unwrap();
+        blob[8] = Compression::Zstd as u8;
+        let err = envelope::decode(&blob).unwrap_err();
+        assert!(matches!(err, EigenDaError::Codec(_)));
+    }
 
-        let batch_header_hash = blob_status.batch_header_hash().unwrap();
-        let blob_index = blob_status.blob_index().unwrap();
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_mock_disperse_and_retrieve_compressed() {
+        let server = MockDisperser::new().start().unwrap();
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(TonicTransport::new(server.uri()))
+            .compression(Compression::Zstd)
+            .build()
+            .unwrap();
+        let data = vec![0u8; 4096];
+
+        let blob_response = client.disperse_data(&data).unwrap();
+        let confirmed = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+
+        let blob = client
+            .retrieve_blob(confirmed.batch_header_hash(), confirmed.blob_index())
+            .unwrap();
+        assert!(base64::decode(blob.data()).unwrap().len() < data.len());
+        assert_eq!(DecodedBlob::from_encoded(blob).unwrap().data(), data);
+    }
+
+    #[test]
+    fn test_encrypted_envelope() {
+        let keys = KeyRing::new()
+            .with_key("k1", [1; 32])
+            .with_key("k2", [2; 32]);
+        let key = EncryptionKey::from_provider("k1", &keys).unwrap();
+        let data = b"confidential".to_vec();
+
+        let blob =
+            envelope::encode_with(&data, Format::Raw, Compression::None, Some(&key)).unwrap();
+        let (header, decoded) = envelope::decode_with(&blob, usize::MAX, Some(&keys)).unwrap();
+        assert_eq!(
+            header.encryption(),
+            encryption::Encryption::ChaCha20Poly1305
+        );
+        assert_eq!(decoded, data);
+
+        let err = envelope::decode(&blob).unwrap_err();
+        assert!(matches!(err, EigenDaError::Encryption(_)));
+        assert!(err.to_string().contains("k1"));
+
+        let wrong_keys = KeyRing::new().with_key("k1", [2; 32]);
+        let err = envelope::decode_with(&blob, usize::MAX, Some(&wrong_keys)).unwrap_err();
+        assert!(matches!(err, EigenDaError::Encryption(_)));
+
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        let err = envelope::decode_with(&tampered, usize::MAX, Some(&keys)).unwrap_err();
+        assert!(matches!(err, EigenDaError::Encryption(_)));
+
+        // The header is authenticated too.
+        let mut tampered = blob.clone();
+        tampered[7] = Format::Json as u8;
+        let err = envelope::decode_with(&tampered, usize::MAX, Some(&keys)).unwrap_err();
+        assert!(matches!(err, EigenDaError::Encryption(_)));
+    }
+
+    #[test]
+    fn test_mock_disperse_and_retrieve_encrypted() {
+        let server = MockDisperser::new().start().unwrap();
+        let keys = KeyRing::new().with_key("rollup", [7; 32]);
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(TonicTransport::new(server.uri()))
+            .encryption_key(EncryptionKey::from_provider("rollup", &keys).unwrap())
+            .key_provider(keys.clone())
+            .build()
+            .unwrap();
+        let data = b"not for the public".to_vec();
+
+        let blob_response = client.disperse_data(&data).unwrap();
+        let confirmed = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+        let batch_header_hash = confirmed.batch_header_hash();
+        let blob_index = confirmed.blob_index();
 
         let blob = client.retrieve_blob(batch_header_hash, blob_index).unwrap();
+        assert!(DecodedBlob::from_encoded(blob.clone()).is_err());
+        assert_eq!(
+            DecodedBlob::from_encrypted(blob, &keys).unwrap().data(),
+            data
+        );
+        assert_eq!(
+            client.retrieve_data(batch_header_hash, blob_index).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_manifest_rejects_altered_chunks() {
+        let reference = |index| BlobReference::new(BatchHeaderHash::new([7; 32]), index);
+        let manifest = Manifest::new(vec![
+            ManifestChunk::new(reference(0), b"first"),
+            ManifestChunk::new(reference(1), b"second"),
+        ]);
+        assert_eq!(manifest.length(), 11);
 
-        let blob = EncodedBlob::from_str(&blob).unwrap();
+        let data = manifest
+            .assemble(vec![b"first".to_vec(), b"second".to_vec()])
+            .unwrap();
+        assert_eq!(data, b"firstsecond");
 
-        let decoded_blob = DecodedBlob::from_encoded(blob).unwrap();
-        println!("{}", decoded_blob.len());
+        let err = manifest
+            .assemble(vec![b"first".to_vec(), b"sec0nd".to_vec()])
+            .unwrap_err();
+        assert!(matches!(err, EigenDaError::Codec(_)));
+        assert!(manifest.assemble(vec![b"first".to_vec()]).is_err());
+
+        // A forged length is rejected, not allocated.
+        let mut forged = serde_json::to_value(&manifest).unwrap();
+        forged["length"] = u64::MAX.into();
+        let forged: Manifest = serde_json::from_value(forged).unwrap();
+        let err = forged
+            .assemble(vec![b"first".to_vec(), b"second".to_vec()])
+            .unwrap_err();
+        assert!(matches!(err, EigenDaError::Codec(_)));
     }
 
-    fn create_client() -> EigenDaGrpcClient {
-        EigenDaGrpcClient::default()
+    #[test]
+    fn test_mock_disperse_and_retrieve_chunked() {
+        let (_server, client) = mock_client();
+        let options = ChunkedOptionsBuilder::default()
+            .chunk_size(1000)
+            .max_concurrency(3)
+            .poll_interval(Duration::from_millis(10))
+            .confirmation_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+        let data: Vec<u8> = (0..10_500).map(|i| (i % 251) as u8).collect();
+
+        let manifest = client.disperse_chunked(&data, &options).unwrap();
+        let retrieved = client.retrieve_chunked(&manifest, &options).unwrap();
+        assert_eq!(retrieved, data);
+    }
+
+    #[tokio::test]
+    async fn test_mock_disperse_and_retrieve_chunked_async() {
+        let (_server, client) = mock_async_client();
+        let options = ChunkedOptionsBuilder::default()
+            .chunk_size(1000)
+            .poll_interval(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        let data = vec![0x5a; 4321];
+
+        let manifest = client.disperse_chunked(&data, &options).await.unwrap();
+        let retrieved = client.retrieve_chunked(&manifest, &options).await.unwrap();
+        assert_eq!(retrieved, data);
+    }
+
+    #[test]
+    fn test_blob_packer() {
+        let mut packer = BlobPacker::new(64);
+        assert_eq!(packer.push(b"first").unwrap(), 0);
+        assert_eq!(packer.push(b"").unwrap(), 1);
+        assert_eq!(packer.push(b"third").unwrap(), 2);
+        assert_eq!(packer.packed_len(), 4 + 3 * 4 + 10);
+        assert!(matches!(
+            packer.push(&[0; 64]),
+            Err(EigenDaError::Validation(_))
+        ));
+
+        let packed = packer.pack();
+        assert_eq!(packed.len(), packer.packed_len());
+        assert_eq!(packer::message_count(&packed).unwrap(), 3);
+        assert_eq!(packer::message(&packed, 0).unwrap(), b"first");
+        assert_eq!(packer::message(&packed, 1).unwrap(), b"");
+        assert_eq!(packer::message(&packed, 2).unwrap(), b"third");
+        assert!(packer::message(&packed, 3).is_err());
+        assert!(packer::message(&packed[..packed.len() - 1], 2).is_err());
+    }
+
+    #[test]
+    fn test_mock_disperse_packed_and_retrieve_message() {
+        let (_server, client) = mock_client();
+        let mut packer = BlobPacker::default();
+        for i in 0..100u32 {
+            packer.push(format!("message {}", i).as_bytes()).unwrap();
+        }
+
+        let blob_response = client.disperse_packed(&packer).unwrap();
+        let blob_status = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+        let locators = packer.locators(&blob_status.reference());
+        assert_eq!(locators.len(), 100);
+
+        let message = client.retrieve_message(&locators[42]).unwrap();
+        assert_eq!(message, b"message 42");
+
+        let blob = client
+            .retrieve_blob(
+                locators[0].blob().batch_header_hash(),
+                locators[0].blob().blob_index(),
+            )
+            .unwrap();
+        let decoded = DecodedBlob::from_encoded(blob).unwrap();
+        assert_eq!(decoded.format(), Format::Packed);
+        assert_eq!(decoded.message_count().unwrap(), 100);
+        assert_eq!(decoded.message(99).unwrap(), b"message 99");
+    }
+
+    #[test]
+    fn test_fixed_size_hashes() {
+        let bytes: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let hash = BatchHeaderHash::new(bytes);
+        let hex = format!("0x{}", hex::encode(bytes));
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(hash.to_base64(), base64::encode(bytes));
+
+        assert_eq!(BatchHeaderHash::from_hex(&hex).unwrap(), hash);
+        assert_eq!(BatchHeaderHash::from_hex(&hex[2..]).unwrap(), hash);
+        assert_eq!(hex.parse::<BatchHeaderHash>().unwrap(), hash);
+        assert_eq!(hash.to_base64().parse::<BatchHeaderHash>().unwrap(), hash);
+        assert_ne!(hash, BatchHeaderHash::default());
+        assert!(matches!(
+            BatchHeaderHash::from_hex("0x0102"),
+            Err(EigenDaError::Codec(_))
+        ));
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", base64::encode(bytes)));
+        assert_eq!(
+            serde_json::from_str::<BatchHeaderHash>(&json).unwrap(),
+            hash
+        );
+        assert!(serde_json::from_str::<BatchHeaderHash>("\"AQI=\"").is_err());
+
+        // Base64 of bytes starting 0xD3 0x1_ starts with "0x" too.
+        let mut bytes = [0x70; 32];
+        bytes[..2].copy_from_slice(&[0xd3, 0x1f]);
+        let hash = BatchHeaderHash::new(bytes);
+        let json = serde_json::to_string(&hash).unwrap();
+        assert!(json.starts_with("\"0x"));
+        assert_eq!(
+            serde_json::from_str::<BatchHeaderHash>(&json).unwrap(),
+            hash
+        );
+        assert!(hash.to_base64().starts_with("0x"));
+        assert_eq!(hash.to_base64().parse::<BatchHeaderHash>().unwrap(), hash);
+        assert_eq!(hash.to_hex().parse::<BatchHeaderHash>().unwrap(), hash);
+        assert_eq!(hash.to_hex()[2..].parse::<BatchHeaderHash>().unwrap(), hash);
+    }
+
+    #[test]
+    fn test_signed_percentages_by_quorum() {
+        let header: BatchHeader = serde_json::from_value(serde_json::json!({
+            "batchRoot": base64::encode([0; 32]),
+            "quorumNumbers": base64::encode([0, 1, 5]),
+            "quorumSignedPercentages": base64::encode([100, 90, 67]),
+            "referenceBlockNumber": 1,
+        }))
+        .unwrap();
+        assert_eq!(header.quorum_numbers().as_bytes(), [0, 1, 5]);
+        let by_quorum = header.signed_percentages().unwrap();
+        assert_eq!(
+            by_quorum.into_iter().collect::<Vec<_>>(),
+            [(0, 100), (1, 90), (5, 67)]
+        );
+        assert_eq!(header.signed_percentage(1).unwrap(), Some(90));
+        assert_eq!(header.signed_percentage(2).unwrap(), None);
+
+        let header: BatchHeader = serde_json::from_value(serde_json::json!({
+            "batchRoot": base64::encode([0; 32]),
+            "quorumNumbers": base64::encode([0, 1]),
+            "quorumSignedPercentages": base64::encode([100]),
+            "referenceBlockNumber": 1,
+        }))
+        .unwrap();
+        assert!(matches!(
+            header.signed_percentages(),
+            Err(EigenDaError::Codec(_))
+        ));
+    }
+
+    #[test]
+    fn test_mock_signed_percentages() {
+        let (_server, client) = mock_client();
+
+        let blob_response = client.disperse_data(b"data").unwrap();
+        let blob_status = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+        let by_quorum = blob_status.signed_percentages().unwrap();
+        assert_eq!(
+            by_quorum.into_iter().collect::<Vec<_>>(),
+            [(0, 100), (1, 100)]
+        );
+        assert_eq!(blob_status.signed_percentage(1).unwrap(), Some(100));
+    }
+
+    #[test]
+    fn test_inclusion_proof_siblings() {
+        let bytes: Vec<u8> = (0..96).collect();
+        let proof = BlobInclusionProof::from_bytes(&bytes).unwrap();
+        assert_eq!(proof.depth(), 3);
+        assert_eq!(proof.siblings()[1][0], 32);
+        assert_eq!(proof.to_bytes(), bytes);
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(json, format!("\"{}\"", base64::encode(&bytes)));
+        assert_eq!(
+            serde_json::from_str::<BlobInclusionProof>(&json).unwrap(),
+            proof
+        );
+
+        assert!(matches!(
+            BlobInclusionProof::from_bytes(&bytes[..95]),
+            Err(EigenDaError::Codec(_))
+        ));
+        let json = format!("\"{}\"", base64::encode(&bytes[..40]));
+        assert!(serde_json::from_str::<BlobInclusionProof>(&json).is_err());
+        assert!(proof.root([0; 32], 8).is_err());
+
+        let mut bytes = vec![0x70; 64];
+        bytes[..2].copy_from_slice(&[0xd3, 0x12]);
+        let json = format!("\"{}\"", base64::encode(&bytes));
+        assert!(json.starts_with("\"0x"));
+        let proof = serde_json::from_str::<BlobInclusionProof>(&json).unwrap();
+        assert_eq!(proof.depth(), 2);
+        assert_eq!(proof.to_bytes(), bytes);
+        assert_eq!(
+            base64::encode(&bytes)
+                .parse::<BlobInclusionProof>()
+                .unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn test_mock_inclusion_proof_verifies() {
+        let (_server, client) = mock_client();
+        let blobs: Vec<Vec<u8>> = (1..=3).map(|i| vec![0, i, i, i]).collect();
+
+        let request_ids: Vec<String> = blobs
+            .iter()
+            .map(|blob| {
+                client
+                    .disperse_blob(base64::encode(blob))
+                    .unwrap()
+                    .request_id()
+            })
+            .collect();
+        for (blob, request_id) in blobs.iter().zip(request_ids) {
+            let status = client
+                .wait_for_confirmation(&request_id, Duration::ZERO, None)
+                .unwrap();
+            let proof = status.inclusion_proof();
+            assert_eq!(proof.depth(), 2);
+
+            let leaf: [u8; 32] = Keccak256::digest(blob).into();
+            let root = status.batch_root();
+            assert!(proof.verify(leaf, status.blob_index(), root));
+            assert!(status.verify_inclusion(leaf));
+            assert!(!proof.verify(leaf, status.blob_index() ^ 1, root));
+        }
+    }
+
+    #[test]
+    fn test_blob_commitment_is_checked_on_curve() {
+        let one = FieldElement::from_be_bytes(&[1]).unwrap();
+        let two = FieldElement::from_be_bytes(&[2]).unwrap();
+        let three = FieldElement::from_be_bytes(&[3]).unwrap();
+        let generator = BlobCommitment::new(G1Point::new(one, two).unwrap());
+        assert_eq!(
+            BlobCommitment::from_bytes(&generator.to_bytes()).unwrap(),
+            generator
+        );
+        assert!(matches!(
+            G1Point::new(one, three),
+            Err(EigenDaError::Codec(_))
+        ));
+        assert!(
+            G1Point::new(FieldElement::default(), FieldElement::default())
+                .unwrap()
+                .is_infinity()
+        );
+        assert!(FieldElement::from_be_bytes(&[0xff; 32]).is_err());
+
+        let json = serde_json::to_string(&generator).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BlobCommitment>(&json).unwrap(),
+            generator
+        );
+        let off_curve = format!(
+            r#"{{"x":"{}","y":"{}"}}"#,
+            base64::encode([1]),
+            base64::encode([3])
+        );
+        assert!(serde_json::from_str::<BlobCommitment>(&off_curve).is_err());
+    }
+
+    #[test]
+    fn test_mock_blob_commitment_is_on_curve() {
+        let (_server, client) = mock_client();
+
+        let blob_response = client.disperse_data(b"data").unwrap();
+        let blob_status = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+        let commitment = blob_status.commitment();
+        assert!(commitment.point().is_on_curve());
+        assert_eq!(commitment.x().to_be_bytes()[31], 1);
+        assert_eq!(commitment.y().to_be_bytes()[31], 2);
+        assert_eq!(commitment.to_bytes().len(), 64);
+    }
+
+    #[test]
+    fn test_blob_result_covers_every_status() {
+        for (name, result) in [
+            ("UNKNOWN", BlobResult::Unknown),
+            ("PROCESSING", BlobResult::Processing),
+            ("CONFIRMED", BlobResult::Confirmed),
+            ("FAILED", BlobResult::Failed),
+            ("FINALIZED", BlobResult::Finalized),
+            (
+                "INSUFFICIENT_SIGNATURES",
+                BlobResult::InsufficientSignatures,
+            ),
+            ("DISPERSING", BlobResult::Dispersing),
+        ] {
+            let json = format!("\"{}\"", name);
+            assert_eq!(serde_json::from_str::<BlobResult>(&json).unwrap(), result);
+            assert_eq!(serde_json::to_string(&result).unwrap(), json);
+        }
+        assert_eq!(
+            serde_json::from_str::<BlobResult>("5").unwrap(),
+            BlobResult::InsufficientSignatures
+        );
+
+        let status: BlobStatus = r#"{"status": "REQUEUED"}"#.parse().unwrap();
+        assert_eq!(status.status(), &BlobResult::Other("REQUEUED".to_string()));
+        assert!(!status.status().is_terminal());
+        assert_eq!(
+            serde_json::to_string(status.status()).unwrap(),
+            "\"REQUEUED\""
+        );
+
+        assert!(BlobResult::Dispersing.is_pending());
+        assert!(BlobResult::Finalized.is_success());
+        assert!(BlobResult::InsufficientSignatures.is_failure());
+        assert!(BlobResult::InsufficientSignatures.is_terminal());
+        assert!(!BlobResult::Unknown.is_terminal());
+    }
+
+    #[test]
+    fn test_parse_replies_fails_with_the_body() {
+        let response = BlobResponse::try_from(
+            "Resolved method\n{\"result\": \"PROCESSING\", \"requestId\": \"aWQ=\"}".to_string(),
+        )
+        .unwrap();
+        assert_eq!(response.result(), &BlobResult::Processing);
+        assert_eq!(response.request_id(), "aWQ=");
+
+        let body = "{\"result\": \"PROCESSING\"}".to_string();
+        match BlobResponse::try_from(body.clone()) {
+            Err(EigenDaError::Json { body: raw, .. }) => assert_eq!(raw, body),
+            other => panic!("expected a JSON error, got {:?}", other),
+        }
+
+        let body = "connection reset".to_string();
+        match BlobStatus::try_from(body.clone()) {
+            Err(EigenDaError::Json { body: raw, .. }) => assert_eq!(raw, body),
+            other => panic!("expected a JSON error, got {:?}", other),
+        }
+        let status = BlobStatus::try_from("{\"status\": \"DISPERSING\"}".to_string()).unwrap();
+        assert_eq!(status.status(), &BlobResult::Dispersing);
+    }
+
+    #[test]
+    fn test_proto_integer_widths() {
+        let params: BlobQuorumParams = serde_json::from_str(
+            r#"{"quorumNumber": 1, "adversaryThresholdPercentage": "33",
+                "quorumThresholdPercentage": 55, "encodedLength": "4294967296"}"#,
+        )
+        .unwrap();
+        assert_eq!(params.quorum_number(), 1);
+        assert_eq!(params.adversary_threshold_percentage(), 33);
+        assert_eq!(params.quantization_param(), None);
+        assert_eq!(params.encoded_length(), Some(1 << 32));
+
+        let out_of_range = r#"{"quorumNumber": 4294967296, "adversaryThresholdPercentage": 33,
+            "quorumThresholdPercentage": 55}"#;
+        assert!(serde_json::from_str::<BlobQuorumParams>(out_of_range).is_err());
+        let negative = r#"{"quorumNumber": -1, "adversaryThresholdPercentage": 33,
+            "quorumThresholdPercentage": 55}"#;
+        assert!(serde_json::from_str::<BlobQuorumParams>(negative).is_err());
+
+        let hash = base64::encode([0; 32]);
+        let proof = |blob_index: &str| {
+            serde_json::from_str::<BlobVerificationProof>(&format!(
+                r#"{{"batchId": "7", {} "batchMetadata": {{"batchHeader": {{
+                    "batchRoot": "{hash}", "quorumNumbers": "", "quorumSignedPercentages": "",
+                    "referenceBlockNumber": 0}},
+                    "signatoryRecordHash": "{hash}", "fee": "", "confirmationBlockNumber": "0",
+                    "batchHeaderHash": "{hash}"}}, "quorumIndexes": ""}}"#,
+                blob_index,
+                hash = hash
+            ))
+        };
+        assert_eq!(proof("").unwrap().blob_index(), None);
+        assert_eq!(proof(r#""blobIndex": 0,"#).unwrap().blob_index(), Some(0));
+        assert_eq!(proof(r#""blobIndex": "3","#).unwrap().batch_id(), 7);
+        assert!(proof(r#""blobIndex": "x","#).is_err());
+    }
+
+    #[test]
+    fn test_blob_state() {
+        let state = |json: &str| json.parse::<BlobStatus>().unwrap().into_state();
+        assert!(matches!(
+            state(r#"{"status": "DISPERSING"}"#),
+            Ok(BlobState::Pending(BlobResult::Dispersing))
+        ));
+        assert!(matches!(
+            state(r#"{"status": "INSUFFICIENT_SIGNATURES"}"#),
+            Ok(BlobState::Failed(BlobResult::InsufficientSignatures))
+        ));
+        assert!(matches!(
+            state(r#"{"status": "UNKNOWN"}"#),
+            Ok(BlobState::Unknown(BlobResult::Unknown))
+        ));
+        assert!(matches!(
+            state(r#"{"status": "REQUEUED"}"#),
+            Ok(BlobState::Unknown(BlobResult::Other(_)))
+        ));
+        assert!(matches!(
+            state(r#"{"status": "CONFIRMED"}"#),
+            Err(EigenDaError::Dispersal(_))
+        ));
+
+        let (_server, client) = mock_client();
+        let blob_response = client.disperse_data(b"data").unwrap();
+        let confirmed = client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+        assert!(!confirmed.is_finalized());
+        assert_eq!(
+            client
+                .retrieve_data(confirmed.batch_header_hash(), confirmed.blob_index())
+                .unwrap(),
+            b"data"
+        );
+
+        let finalized = client
+            .get_blob_status(&blob_response.request_id())
+            .unwrap()
+            .into_state()
+            .unwrap()
+            .into_confirmed()
+            .unwrap();
+        assert!(finalized.is_finalized());
+        assert_eq!(finalized.reference(), confirmed.reference());
+    }
+
+    #[tokio::test]
+    async fn test_blocking_client_on_blocking_threads() {
+        let (_server, client) = mock_client();
+
+        let client = tokio::task::spawn_blocking(move || {
+            client.disperse_blob(base64::encode([0, 1, 2, 3])).unwrap();
+            client
+        })
+        .await
+        .unwrap();
+        // Drops the transport's runtime on an async task.
+        tokio::spawn(async move { drop(client) }).await.unwrap();
+    }
+
+    #[test]
+    fn test_tonic_transport_reuses_its_channel() {
+        let server = MockDisperser::new().start().unwrap();
+        let transport = TonicTransport::new(server.uri());
+        assert!(!transport.is_connected());
+        let client = EigenDaGrpcClientBuilder::default()
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        let blob_response = client.disperse_data(b"data").unwrap();
+        client
+            .wait_for_confirmation(&blob_response.request_id(), Duration::ZERO, None)
+            .unwrap();
+        // Clones share the channel the client connected.
+        assert!(transport.is_connected());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_set_server_address() {
+        let server = MockDisperser::new().start().unwrap();
+        let mut client = EigenDaGrpcClientBuilder::default()
+            .transport(TonicTransport::new("http://127.0.0.1:1".to_string()))
+            .build()
+            .unwrap();
+        client.set_server_address(&server.uri()).unwrap();
+        client.disperse_data(b"data").unwrap();
+
+        // With the grpcurl feature the builder setter picks grpcurl, which
+        // the tests do not have.
+        #[cfg(not(feature = "grpcurl"))]
+        {
+            let mut client = EigenDaGrpcClientBuilder::default()
+                .server_address("http://127.0.0.1:1".to_string())
+                .build()
+                .unwrap();
+            client.update_server_address(server.uri());
+            client.disperse_data(b"data").unwrap();
+        }
+
+        let (mut client, _) = flaky_client(tonic::Code::Unavailable, 0);
+        assert!(matches!(
+            client.set_server_address(&server.uri()),
+            Err(EigenDaError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_wait_for_confirmation_stops_on_unknown_status() {
+        // The flaky transport answers with a default status, `UNKNOWN`.
+        let (client, calls) = flaky_client(tonic::Code::Unavailable, 0);
+        assert!(matches!(
+            client.wait_for_confirmation("aWQ=", Duration::ZERO, None),
+            Err(EigenDaError::Dispersal(_))
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let options = ChunkedOptionsBuilder::default().build().unwrap();
+        assert_eq!(
+            options.confirmation_timeout(),
+            Some(chunked::DEFAULT_CONFIRMATION_TIMEOUT)
+        );
+        let options = ChunkedOptionsBuilder::default()
+            .no_confirmation_timeout()
+            .build()
+            .unwrap();
+        assert_eq!(options.confirmation_timeout(), None);
     }
 }
@@ -9,7 +9,8 @@ pub struct BatchMetadata {
     batch_header: BatchHeader,
     signatory_record_hash: BlobSignatoryRecordHash,
     fee: BlobFee,
-    confirmation_block_number: u128,
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    confirmation_block_number: u32,
     batch_header_hash: BatchHeaderHash,
 }
 
@@ -26,7 +27,7 @@ impl BatchMetadata {
         &self.fee
     }
 
-    pub fn confirmation_block_number(&self) -> u128 {
+    pub fn confirmation_block_number(&self) -> u32 {
         self.confirmation_block_number
     }
 
@@ -1,20 +1,22 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EigenDaGrpcMethod {
     DisperseBlob,
+    DisperseBlobAuthenticated,
     GetBlobStatus,
+    RetrieveBlob,
 }
 
-impl ToString for EigenDaGrpcMethod {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for EigenDaGrpcMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            EigenDaGrpcMethod::DisperseBlob => {
-                return "disperser.Disperser/DisperseBlob".to_string()
-            }
-            EigenDaGrpcMethod::GetBlobStatus => {
-                return "disperser.Disperser/GetBlobStatus".to_string()
+            EigenDaGrpcMethod::DisperseBlob => write!(f, "disperser.Disperser/DisperseBlob"),
+            EigenDaGrpcMethod::DisperseBlobAuthenticated => {
+                write!(f, "disperser.Disperser/DisperseBlobAuthenticated")
             }
+            EigenDaGrpcMethod::GetBlobStatus => write!(f, "disperser.Disperser/GetBlobStatus"),
+            EigenDaGrpcMethod::RetrieveBlob => write!(f, "disperser.Disperser/RetrieveBlob"),
         }
     }
 }
@@ -0,0 +1,511 @@
+//! An in-process fake of the EigenDA disperser, for exercising the client
+//! without network access. Enable the `mock` feature to use it from tests in
+//! other crates.
+//!
+//! Blobs move from `PROCESSING` to `CONFIRMED` to `FINALIZED` as their status
+//! is polled. On confirmation every blob still processing is put in one
+//! batch, with a keccak Merkle tree over the blobs giving each its inclusion
+//! proof. `DisperseBlobAuthenticated` challenges the client and checks the
+//! signature against the account id before dispersing.
+use crate::codec::is_field_element_safe;
+use crate::grpc::common::G1Commitment;
+use crate::grpc::disperser::authenticated_request::Payload;
+use crate::grpc::disperser::disperser_server::{Disperser, DisperserServer};
+use crate::grpc::disperser::{
+    authenticated_reply, AuthenticatedReply, AuthenticatedRequest, BatchHeader, BatchMetadata,
+    BlobAuthHeader, BlobHeader, BlobInfo, BlobQuorumParam, BlobStatus, BlobStatusReply,
+    BlobStatusRequest, BlobVerificationProof, DisperseBlobReply, DisperseBlobRequest,
+    RetrieveBlobReply, RetrieveBlobRequest,
+};
+use crate::payload::MAX_QUORUM_ID;
+use crate::signer::challenge_digest;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
+
+/// The largest blob the mock accepts, matching the public dispersers.
+pub const MOCK_MAX_BLOB_SIZE: usize = 2 * 1024 * 1024;
+
+/// The quorums every mock blob is dispersed to.
+pub const MOCK_QUORUM_NUMBERS: [u8; 2] = [0, 1];
+
+#[derive(Debug)]
+struct MockBlob {
+    data: Vec<u8>,
+    /// The required quorums followed by any custom ones.
+    quorums: Vec<u8>,
+    status: BlobStatus,
+    polls: u32,
+    info: Option<BlobInfo>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    blobs: HashMap<Vec<u8>, MockBlob>,
+    /// Request ids in dispersal order, so batches keep blob order.
+    order: Vec<Vec<u8>>,
+    /// Stored blob data by `(batch_header_hash, blob_index)`.
+    batches: HashMap<(Vec<u8>, u32), Vec<u8>>,
+    next_request: u64,
+    next_batch_id: u32,
+    next_challenge: u32,
+    block_number: u32,
+}
+
+/// The fake disperser service. Serve it with [`MockDisperser::start`].
+#[derive(Clone, Debug)]
+pub struct MockDisperser {
+    state: Arc<Mutex<MockState>>,
+    polls_per_transition: u32,
+    reply_delay: Duration,
+}
+
+impl Default for MockDisperser {
+    fn default() -> Self {
+        MockDisperser {
+            state: Default::default(),
+            polls_per_transition: 1,
+            reply_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl MockDisperser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many status polls a blob stays in each state before moving on.
+    pub fn polls_per_transition(mut self, polls: u32) -> Self {
+        self.polls_per_transition = polls.max(1);
+        self
+    }
+
+    /// How long the mock waits before answering `DisperseBlob`,
+    /// `GetBlobStatus` and `RetrieveBlob`, to exercise client timeouts.
+    pub fn reply_delay(mut self, delay: Duration) -> Self {
+        self.reply_delay = delay;
+        self
+    }
+
+    /// Serve the mock on a local port from a dedicated thread, so it can be
+    /// used from both blocking and async tests.
+    pub fn start(self) -> Result<MockServer, std::io::Error> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        std::thread::spawn(move || {
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener)
+                    .expect("failed to register mock disperser listener.");
+                let served = tonic::transport::Server::builder()
+                    .add_service(DisperserServer::new(self))
+                    .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                        shutdown_signal.await.ok();
+                    })
+                    .await;
+                if let Err(err) = served {
+                    log::error!("mock disperser stopped: {}", err);
+                }
+            })
+        });
+
+        Ok(MockServer {
+            address,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Validate and store a dispersed blob as `PROCESSING`, returning the
+    /// `InvalidArgument` message the disperser would reply with otherwise.
+    fn store(&self, request: DisperseBlobRequest) -> Result<DisperseBlobReply, String> {
+        Self::validate(&request.data)?;
+        let mut quorums = MOCK_QUORUM_NUMBERS.to_vec();
+        for (index, quorum) in request.custom_quorum_numbers.iter().enumerate() {
+            if *quorum > MAX_QUORUM_ID {
+                return Err(format!("invalid quorum number {}", quorum));
+            }
+            if request.custom_quorum_numbers[..index].contains(quorum) {
+                return Err(format!("duplicate quorum number {}", quorum));
+            }
+            if !quorums.contains(&(*quorum as u8)) {
+                quorums.push(*quorum as u8);
+            }
+        }
+
+        let mut state = self.state.lock().expect("mock disperser state poisoned.");
+        let request_id = format!("mock-request-{}", state.next_request).into_bytes();
+        state.next_request += 1;
+        state.order.push(request_id.clone());
+        state.blobs.insert(
+            request_id.clone(),
+            MockBlob {
+                data: request.data,
+                quorums,
+                status: BlobStatus::Processing,
+                polls: 0,
+                info: None,
+            },
+        );
+
+        Ok(DisperseBlobReply {
+            result: BlobStatus::Processing as i32,
+            request_id,
+        })
+    }
+
+    fn next_challenge(&self) -> u32 {
+        let mut state = self.state.lock().expect("mock disperser state poisoned.");
+        state.next_challenge = state.next_challenge.wrapping_add(0x9e37_79b9);
+        state.next_challenge
+    }
+
+    /// Check a blob the way the disperser does, returning the message of
+    /// the `InvalidArgument` status it would reply with.
+    fn validate(data: &[u8]) -> Result<(), String> {
+        if data.is_empty() {
+            return Err("blob size must be greater than 0".to_string());
+        }
+        if data.len() > MOCK_MAX_BLOB_SIZE {
+            return Err(format!(
+                "blob size cannot exceed {} bytes",
+                MOCK_MAX_BLOB_SIZE
+            ));
+        }
+        if !is_field_element_safe(data) {
+            return Err("encountered an error to convert a 32-bytes into a valid field element, please use the correct format where every 32bytes(big-endian) is less than 21888242871839275222246405745257275088548364400416034343698204186575808495617".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl MockState {
+    /// Put every processing blob in a new batch and mark them confirmed.
+    fn confirm_batch(&mut self) {
+        let pending: Vec<Vec<u8>> = self
+            .order
+            .iter()
+            .filter(|id| self.blobs[*id].status == BlobStatus::Processing)
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+
+        let leaves: Vec<[u8; 32]> = pending
+            .iter()
+            .map(|id| Keccak256::digest(&self.blobs[id].data).into())
+            .collect();
+        let (batch_root, proofs) = merkle_tree(&leaves);
+        let mut quorum_numbers: Vec<u8> = pending
+            .iter()
+            .flat_map(|id| self.blobs[id].quorums.iter().copied())
+            .collect();
+        quorum_numbers.sort_unstable();
+        quorum_numbers.dedup();
+
+        self.block_number += 1;
+        let reference_block_number = self.block_number;
+        self.block_number += 1;
+        let confirmation_block_number = self.block_number;
+        let batch_id = self.next_batch_id;
+        self.next_batch_id += 1;
+
+        let batch_header = BatchHeader {
+            batch_root: batch_root.to_vec(),
+            quorum_signed_percentages: vec![100; quorum_numbers.len()],
+            quorum_numbers: quorum_numbers.clone(),
+            reference_block_number,
+        };
+        let mut hasher = Keccak256::new();
+        hasher.update(batch_root);
+        hasher.update(reference_block_number.to_be_bytes());
+        let batch_header_hash = hasher.finalize().to_vec();
+        let batch_metadata = BatchMetadata {
+            batch_header: Some(batch_header),
+            signatory_record_hash: Keccak256::digest(&batch_header_hash).to_vec(),
+            fee: vec![0],
+            confirmation_block_number,
+            batch_header_hash: batch_header_hash.clone(),
+        };
+
+        for (index, (id, proof)) in pending.iter().zip(proofs).enumerate() {
+            let blob_index = index as u32;
+            let blob = self
+                .blobs
+                .get_mut(id)
+                .expect("pending blob missing from mock state.");
+            blob.status = BlobStatus::Confirmed;
+            // The poll that confirmed the batch counts towards the next state.
+            blob.polls = 1;
+            blob.info = Some(BlobInfo {
+                blob_header: Some(BlobHeader {
+                    commitment: Some(G1Commitment {
+                        x: g1_generator_coordinate(1),
+                        y: g1_generator_coordinate(2),
+                    }),
+                    data_length: blob.data.len().div_ceil(32) as u32,
+                    blob_quorum_params: blob
+                        .quorums
+                        .iter()
+                        .map(|quorum| BlobQuorumParam {
+                            quorum_number: *quorum as u32,
+                            adversary_threshold_percentage: 33,
+                            quorum_threshold_percentage: 55,
+                            quantization_param: 1,
+                            encoded_length: blob.data.len().div_ceil(32).next_power_of_two() as u64,
+                        })
+                        .collect(),
+                }),
+                blob_verification_proof: Some(BlobVerificationProof {
+                    batch_id,
+                    blob_index,
+                    batch_metadata: Some(batch_metadata.clone()),
+                    inclusion_proof: proof.concat(),
+                    quorum_indexes: blob
+                        .quorums
+                        .iter()
+                        .map(|quorum| {
+                            quorum_numbers
+                                .iter()
+                                .position(|batch_quorum| batch_quorum == quorum)
+                                .expect("blob quorum missing from batch.")
+                                as u8
+                        })
+                        .collect(),
+                }),
+            });
+            self.batches
+                .insert((batch_header_hash.clone(), blob_index), blob.data.clone());
+        }
+    }
+}
+
+/// Parse an account id, the hex of an uncompressed secp256k1 public key.
+fn parse_account_id(account_id: &str) -> Option<VerifyingKey> {
+    let bytes = hex::decode(account_id.trim_start_matches("0x")).ok()?;
+    VerifyingKey::from_sec1_bytes(&bytes).ok()
+}
+
+/// Check that `signature` is `account_key`'s `r || s || v` signature of the
+/// challenge.
+fn verify_challenge(account_key: &VerifyingKey, challenge: u32, signature: &[u8]) -> bool {
+    if signature.len() != 65 {
+        return false;
+    }
+    let (Ok(parsed), Some(recovery_id)) = (
+        Signature::from_slice(&signature[..64]),
+        RecoveryId::from_byte(signature[64]),
+    ) else {
+        return false;
+    };
+    VerifyingKey::recover_from_prehash(&challenge_digest(challenge), &parsed, recovery_id)
+        .is_ok_and(|key| &key == account_key)
+}
+
+/// The big-endian bytes of a small G1 generator coordinate, a point that is
+/// on the BN254 curve.
+fn g1_generator_coordinate(value: u8) -> Vec<u8> {
+    let mut coordinate = vec![0u8; 32];
+    coordinate[31] = value;
+    coordinate
+}
+
+/// Build a keccak Merkle tree over `leaves`, padded with zero leaves to a
+/// power of two. Returns the root and, per leaf, its sibling hashes from
+/// the bottom up.
+fn merkle_tree(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+    let mut level = leaves.to_vec();
+    level.resize(leaves.len().next_power_of_two(), [0u8; 32]);
+    let mut proofs = vec![Vec::new(); leaves.len()];
+    let mut positions: Vec<usize> = (0..leaves.len()).collect();
+
+    while level.len() > 1 {
+        for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+            proof.push(level[*position ^ 1]);
+            *position /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Keccak256::new();
+                hasher.update(pair[0]);
+                hasher.update(pair[1]);
+                hasher.finalize().into()
+            })
+            .collect();
+    }
+
+    (level[0], proofs)
+}
+
+#[tonic::async_trait]
+impl Disperser for MockDisperser {
+    async fn disperse_blob(
+        &self,
+        request: Request<DisperseBlobRequest>,
+    ) -> Result<Response<DisperseBlobReply>, Status> {
+        tokio::time::sleep(self.reply_delay).await;
+        let reply = self
+            .store(request.into_inner())
+            .map_err(Status::invalid_argument)?;
+        Ok(Response::new(reply))
+    }
+
+    type DisperseBlobAuthenticatedStream =
+        Pin<Box<dyn Stream<Item = Result<AuthenticatedReply, Status>> + Send + 'static>>;
+
+    async fn disperse_blob_authenticated(
+        &self,
+        request: Request<Streaming<AuthenticatedRequest>>,
+    ) -> Result<Response<Self::DisperseBlobAuthenticatedStream>, Status> {
+        let mut requests = request.into_inner();
+        let (sender, receiver) = mpsc::channel(2);
+        let disperser = self.clone();
+
+        tokio::spawn(async move {
+            let reply = async {
+                let request = match requests.message().await? {
+                    Some(AuthenticatedRequest {
+                        payload: Some(Payload::DisperseRequest(request)),
+                    }) => request,
+                    _ => {
+                        return Err(Status::invalid_argument(
+                            "expected a DisperseBlobRequest as the first message",
+                        ))
+                    }
+                };
+                let account_key = parse_account_id(&request.account_id)
+                    .ok_or_else(|| Status::invalid_argument("invalid account id"))?;
+
+                let challenge = disperser.next_challenge();
+                sender
+                    .send(Ok(AuthenticatedReply {
+                        payload: Some(authenticated_reply::Payload::BlobAuthHeader(
+                            BlobAuthHeader {
+                                challenge_parameter: challenge,
+                            },
+                        )),
+                    }))
+                    .await
+                    .map_err(|_| Status::cancelled("client closed the stream"))?;
+
+                let signature = match requests.message().await? {
+                    Some(AuthenticatedRequest {
+                        payload: Some(Payload::AuthenticationData(data)),
+                    }) => data.authentication_data,
+                    _ => {
+                        return Err(Status::invalid_argument(
+                            "expected AuthenticationData as the second message",
+                        ))
+                    }
+                };
+                if !verify_challenge(&account_key, challenge, &signature) {
+                    return Err(Status::unauthenticated(
+                        "failed to verify the signed challenge",
+                    ));
+                }
+
+                disperser.store(request).map_err(Status::invalid_argument)
+            }
+            .await;
+
+            let reply = reply.map(|reply| AuthenticatedReply {
+                payload: Some(authenticated_reply::Payload::DisperseReply(reply)),
+            });
+            sender.send(reply).await.ok();
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+
+    async fn get_blob_status(
+        &self,
+        request: Request<BlobStatusRequest>,
+    ) -> Result<Response<BlobStatusReply>, Status> {
+        tokio::time::sleep(self.reply_delay).await;
+        let request_id = request.into_inner().request_id;
+        let mut state = self.state.lock().expect("mock disperser state poisoned.");
+
+        let blob = state
+            .blobs
+            .get_mut(&request_id)
+            .ok_or_else(|| Status::not_found("no blob found for the given request id"))?;
+        blob.polls += 1;
+        if blob.polls > self.polls_per_transition {
+            match blob.status {
+                BlobStatus::Processing => state.confirm_batch(),
+                BlobStatus::Confirmed => blob.status = BlobStatus::Finalized,
+                _ => {}
+            }
+        }
+
+        let blob = &state.blobs[&request_id];
+        Ok(Response::new(BlobStatusReply {
+            status: blob.status as i32,
+            info: blob.info.clone(),
+        }))
+    }
+
+    async fn retrieve_blob(
+        &self,
+        request: Request<RetrieveBlobRequest>,
+    ) -> Result<Response<RetrieveBlobReply>, Status> {
+        tokio::time::sleep(self.reply_delay).await;
+        let request = request.into_inner();
+        let state = self.state.lock().expect("mock disperser state poisoned.");
+        let data = state
+            .batches
+            .get(&(request.batch_header_hash, request.blob_index))
+            .ok_or_else(|| Status::not_found("no blob found for the given batch and index"))?;
+
+        // Like the real disperser, hand back the blob padded out to whole
+        // field elements.
+        let mut data = data.clone();
+        data.resize(data.len().div_ceil(32) * 32, 0);
+        Ok(Response::new(RetrieveBlobReply { data }))
+    }
+}
+
+/// A running [`MockDisperser`]. The server shuts down when this is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The plaintext URI of the server, to pass to
+    /// [`TonicTransport::new`](crate::native::TonicTransport::new).
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        // The server drains open connections before it exits, and those may
+        // be driven by a runtime the caller is blocking, so don't wait for it.
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use crate::batch::BatchHeaderHash;
+use crate::blob::EncodedBlob;
+use crate::client::DEFAULT_EIGENDA_SERVER_ADDRESS;
+use crate::error::EigenDaError;
+use crate::grpc::disperser::authenticated_request::Payload;
+use crate::grpc::disperser::disperser_client::DisperserClient;
+use crate::grpc::disperser::{
+    authenticated_reply, AuthenticatedReply, AuthenticatedRequest, AuthenticationData,
+    BlobStatusRequest, DisperseBlobRequest, RetrieveBlobRequest,
+};
+use crate::payload::EigenDaBlobPayload;
+use crate::reply::parse_reply;
+use crate::response::BlobResponse;
+use crate::result::Result;
+use crate::signer::Signer;
+use crate::status::BlobStatus;
+use crate::timeout::{with_timeout, Timeouts};
+use crate::transport::{AsyncDisperserTransport, DisperserTransport};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use tokio::runtime::{Handle, Runtime};
+use tokio::sync::OnceCell;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+/// Sends disperser requests over a native tonic gRPC channel.
+///
+/// The channel is connected on the first request and reused by every later
+/// one, and by clones of the transport. Requests from the blocking client
+/// run on a runtime owned by the transport.
+#[derive(Clone, Debug)]
+pub struct TonicTransport {
+    server_address: String,
+    channel: Arc<OnceCell<Channel>>,
+    runtime: Arc<OnceLock<BlockingRuntime>>,
+}
+
+/// The runtime of the blocking client. It is shut down without waiting for
+/// its tasks, so the last clone of a transport may be dropped in async code,
+/// where dropping a runtime would panic.
+#[derive(Debug)]
+struct BlockingRuntime(Option<Runtime>);
+
+impl BlockingRuntime {
+    fn runtime(&self) -> &Runtime {
+        self.0.as_ref().expect("runtime is only taken on drop.")
+    }
+}
+
+impl Drop for BlockingRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl Default for TonicTransport {
+    fn default() -> Self {
+        TonicTransport::new(DEFAULT_EIGENDA_SERVER_ADDRESS.to_string())
+    }
+}
+
+impl TonicTransport {
+    pub fn new(server_address: String) -> Self {
+        TonicTransport {
+            server_address,
+            channel: Arc::new(OnceCell::new()),
+            runtime: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn server_address(&self) -> &str {
+        &self.server_address
+    }
+
+    /// Send later requests to `address`. The transport reconnects on its
+    /// next request; clones made before keep the old address.
+    pub fn set_server_address(&mut self, address: String) {
+        self.server_address = address;
+        self.channel = Arc::new(OnceCell::new());
+    }
+
+    /// The URI of the disperser. Addresses without a scheme are assumed to
+    /// be served over TLS, as the public EigenDA dispersers are.
+    fn server_uri(&self) -> String {
+        if self.server_address.contains("://") {
+            self.server_address.clone()
+        } else {
+            format!("https://{}", self.server_address)
+        }
+    }
+
+    /// Whether the channel was connected by an earlier request.
+    pub fn is_connected(&self) -> bool {
+        self.channel.initialized()
+    }
+
+    /// A client on the transport's channel, connecting it if this is the
+    /// first request. The connect timeout only applies to that connection,
+    /// tonic reconnects a dropped channel by itself.
+    async fn connect(&self, timeouts: &Timeouts) -> Result<DisperserClient<Channel>> {
+        let channel = self
+            .channel
+            .get_or_try_init(|| self.connect_channel(timeouts))
+            .await?;
+        Ok(DisperserClient::new(channel.clone()))
+    }
+
+    async fn connect_channel(&self, timeouts: &Timeouts) -> Result<Channel> {
+        let uri = self.server_uri();
+        let mut endpoint = Endpoint::from_shared(uri.clone()).map_err(|err| {
+            EigenDaError::validation(format!("invalid server address {uri}: {err}"))
+        })?;
+        if uri.starts_with("https://") {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+        }
+        with_timeout(timeouts.connect(), async {
+            endpoint.connect().await.map_err(|err| {
+                EigenDaError::transport(format!("failed to connect to {uri}: {err}"))
+            })
+        })
+        .await
+    }
+
+    /// Drive a native gRPC request to completion on the transport's runtime,
+    /// so the transport can be used from synchronous code. Inside another
+    /// runtime, such as in `spawn_blocking`, the request is driven from a
+    /// thread of its own, since a runtime cannot be blocked on from within
+    /// one. The calling thread still blocks: prefer
+    /// [`AsyncDisperserTransport`] on async tasks.
+    fn block_on<F, T>(&self, future: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>> + Send,
+        T: Send,
+    {
+        let runtime = self.runtime()?;
+        if Handle::try_current().is_err() {
+            return runtime.block_on(future);
+        }
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| runtime.block_on(future))
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    }
+
+    fn runtime(&self) -> Result<&Runtime> {
+        let runtime = match self.runtime.get() {
+            Some(runtime) => runtime,
+            None => {
+                // The channel's background task lives on this runtime, so it
+                // gets a worker thread of its own to keep serving clones of
+                // the transport used from async code.
+                let runtime = BlockingRuntime(Some(
+                    tokio::runtime::Builder::new_multi_thread()
+                        .worker_threads(1)
+                        .enable_all()
+                        .build()
+                        .map_err(EigenDaError::transport)?,
+                ));
+                self.runtime.get_or_init(|| runtime)
+            }
+        };
+        Ok(runtime.runtime())
+    }
+}
+
+#[async_trait]
+impl AsyncDisperserTransport for TonicTransport {
+    async fn disperse_blob(
+        &self,
+        payload: &EigenDaBlobPayload,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        with_timeout(timeouts.rpc(), async {
+            let request = disperse_request(payload)?;
+            let reply = self.connect(timeouts).await?.disperse_blob(request).await?;
+
+            parse_reply(reply.into_inner().to_json().to_string())
+        })
+        .await
+    }
+
+    async fn disperse_blob_authenticated(
+        &self,
+        payload: &EigenDaBlobPayload,
+        signer: &dyn Signer,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        with_timeout(timeouts.rpc(), async {
+            let mut request = disperse_request(payload)?;
+            request.account_id = signer.account_id();
+
+            // The disperser answers the request with a nonce challenge, which
+            // has to be signed and sent back on the same stream before it
+            // disperses.
+            let (sender, receiver) = tokio::sync::mpsc::channel(2);
+            send_authenticated(&sender, Payload::DisperseRequest(request)).await?;
+            let mut replies = self
+                .connect(timeouts)
+                .await?
+                .disperse_blob_authenticated(ReceiverStream::new(receiver))
+                .await?
+                .into_inner();
+
+            let challenge = match replies.message().await? {
+                Some(AuthenticatedReply {
+                    payload: Some(authenticated_reply::Payload::BlobAuthHeader(header)),
+                }) => header.challenge_parameter,
+                _ => {
+                    return Err(EigenDaError::Protocol(
+                        "expected a challenge from the disperser".to_string(),
+                    ))
+                }
+            };
+            let authentication_data = AuthenticationData {
+                authentication_data: signer.sign_challenge(challenge)?,
+            };
+            send_authenticated(&sender, Payload::AuthenticationData(authentication_data)).await?;
+
+            match replies.message().await? {
+                Some(AuthenticatedReply {
+                    payload: Some(authenticated_reply::Payload::DisperseReply(reply)),
+                }) => parse_reply(reply.to_json().to_string()),
+                _ => Err(EigenDaError::Protocol(
+                    "expected a dispersal reply from the disperser".to_string(),
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn get_blob_status(&self, request_id: &str, timeouts: &Timeouts) -> Result<BlobStatus> {
+        with_timeout(timeouts.rpc(), async {
+            let request = BlobStatusRequest {
+                request_id: base64::decode(request_id)?,
+            };
+            let reply = self
+                .connect(timeouts)
+                .await?
+                .get_blob_status(request)
+                .await?;
+
+            parse_reply(reply.into_inner().to_json().to_string())
+        })
+        .await
+    }
+
+    async fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        timeouts: &Timeouts,
+    ) -> Result<EncodedBlob> {
+        with_timeout(timeouts.rpc(), async {
+            let request = RetrieveBlobRequest {
+                batch_header_hash: batch_header_hash.as_bytes().to_vec(),
+                blob_index,
+            };
+            let reply = self.connect(timeouts).await?.retrieve_blob(request).await?;
+
+            Ok(EncodedBlob::new(base64::encode(reply.into_inner().data)))
+        })
+        .await
+    }
+}
+
+impl DisperserTransport for TonicTransport {
+    fn with_server_address(&self, address: &str) -> Result<Arc<dyn DisperserTransport>> {
+        let mut transport = self.clone();
+        transport.set_server_address(address.to_string());
+        Ok(Arc::new(transport))
+    }
+
+    fn disperse_blob(
+        &self,
+        payload: &EigenDaBlobPayload,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        self.block_on(AsyncDisperserTransport::disperse_blob(
+            self, payload, timeouts,
+        ))
+    }
+
+    fn disperse_blob_authenticated(
+        &self,
+        payload: &EigenDaBlobPayload,
+        signer: &dyn Signer,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        self.block_on(AsyncDisperserTransport::disperse_blob_authenticated(
+            self, payload, signer, timeouts,
+        ))
+    }
+
+    fn get_blob_status(&self, request_id: &str, timeouts: &Timeouts) -> Result<BlobStatus> {
+        self.block_on(AsyncDisperserTransport::get_blob_status(
+            self, request_id, timeouts,
+        ))
+    }
+
+    fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        timeouts: &Timeouts,
+    ) -> Result<EncodedBlob> {
+        self.block_on(AsyncDisperserTransport::retrieve_blob(
+            self,
+            batch_header_hash,
+            blob_index,
+            timeouts,
+        ))
+    }
+}
+
+fn disperse_request(payload: &EigenDaBlobPayload) -> Result<DisperseBlobRequest> {
+    Ok(DisperseBlobRequest {
+        data: base64::decode(payload.data())?,
+        custom_quorum_numbers: payload.options().custom_quorum_numbers().to_vec(),
+        account_id: payload
+            .options()
+            .account_id()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+async fn send_authenticated(
+    sender: &tokio::sync::mpsc::Sender<AuthenticatedRequest>,
+    payload: Payload,
+) -> Result<()> {
+    sender
+        .send(AuthenticatedRequest {
+            payload: Some(payload),
+        })
+        .await
+        .map_err(|_| EigenDaError::transport("authenticated dispersal stream closed"))
+}
@@ -0,0 +1,72 @@
+//! Serde of the disperser's integer fields.
+//!
+//! Proto3 JSON renders `uint64` as strings and `uint32` as numbers, though
+//! either may be sent as the other. Fields are parsed from both forms into
+//! the width the proto declares, rejecting values that do not fit.
+use serde::de::{self, Deserializer, Visitor};
+use std::marker::PhantomData;
+
+struct NumberVisitor<T>(PhantomData<T>);
+
+impl<T: TryFrom<u64>> NumberVisitor<T> {
+    fn fit<E: de::Error>(value: u64) -> Result<T, E> {
+        T::try_from(value).map_err(|_| {
+            E::custom(format!(
+                "{} is out of range for a {}",
+                value,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
+impl<T: TryFrom<u64>> Visitor<'_> for NumberVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "a {}, as a number or a string",
+            std::any::type_name::<T>()
+        )
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        Self::fit(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        let value = u64::try_from(value)
+            .map_err(|_| E::custom(format!("{} is negative, expected an unsigned", value)))?;
+        Self::fit(value)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        let value = value
+            .parse::<u64>()
+            .map_err(|err| E::custom(format!("invalid integer {:?}: {}", value, err)))?;
+        Self::fit(value)
+    }
+}
+
+/// Parse an unsigned integer from a JSON number or string.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u64>,
+{
+    deserializer.deserialize_any(NumberVisitor(PhantomData))
+}
+
+/// [`deserialize`] for optional fields, `None` if the field is `null`. Pair
+/// it with `#[serde(default)]` for fields that may be left out.
+pub fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u64>,
+{
+    #[derive(serde::Deserialize)]
+    struct Number<T: TryFrom<u64>>(#[serde(deserialize_with = "deserialize")] T);
+
+    Ok(<Option<Number<T>> as serde::Deserialize>::deserialize(deserializer)?.map(|n| n.0))
+}
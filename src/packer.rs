@@ -0,0 +1,178 @@
+//! Packing of many small messages into one blob.
+//!
+//! A packed blob is dispersed with [`Format::Packed`](crate::typed::Format::Packed)
+//! and starts with an index table, so a single message can be read without
+//! parsing the others:
+//!
+//! `[message count, u32 BE][end offset of each message, u32 BE][messages]`
+//!
+//! Offsets are counted from the start of the messages.
+use crate::chunked::DEFAULT_CHUNK_SIZE;
+use crate::error::EigenDaError;
+use crate::reference::BlobReference;
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+
+/// The largest packed blob a [`BlobPacker`] builds by default.
+pub const DEFAULT_MAX_PACKED_SIZE: usize = DEFAULT_CHUNK_SIZE;
+
+/// Bytes in the message count and in each index table entry.
+const INDEX_ENTRY_LEN: usize = 4;
+
+/// Where a packed message can be retrieved from: its blob and its index in
+/// the blob.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MessageLocator {
+    blob: BlobReference,
+    index: u32,
+}
+
+impl MessageLocator {
+    pub fn new(blob: BlobReference, index: u32) -> Self {
+        MessageLocator { blob, index }
+    }
+
+    pub fn blob(&self) -> &BlobReference {
+        &self.blob
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+/// Collects messages into a packed blob of at most `max_size` bytes.
+#[derive(Clone, Debug)]
+pub struct BlobPacker {
+    max_size: usize,
+    ends: Vec<u32>,
+    messages: Vec<u8>,
+}
+
+impl Default for BlobPacker {
+    fn default() -> Self {
+        BlobPacker::new(DEFAULT_MAX_PACKED_SIZE)
+    }
+}
+
+impl BlobPacker {
+    pub fn new(max_size: usize) -> Self {
+        BlobPacker {
+            max_size,
+            ends: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// The number of messages packed so far.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// The size of the packed blob as it is now.
+    pub fn packed_len(&self) -> usize {
+        INDEX_ENTRY_LEN * (1 + self.ends.len()) + self.messages.len()
+    }
+
+    /// Whether a message of `len` bytes still fits.
+    pub fn fits(&self, len: usize) -> bool {
+        self.packed_len() + INDEX_ENTRY_LEN + len <= self.max_size
+    }
+
+    /// Add `message`, returning its index in the blob. Fails if it does not
+    /// fit, in which case the packed messages should be dispersed and a new
+    /// packer started.
+    pub fn push(&mut self, message: &[u8]) -> Result<u32> {
+        if !self.fits(message.len()) {
+            return Err(EigenDaError::validation(format!(
+                "message of {} bytes does not fit in the {} bytes left of the packed blob",
+                message.len(),
+                self.max_size
+                    .saturating_sub(self.packed_len() + INDEX_ENTRY_LEN)
+            )));
+        }
+        let end = u32::try_from(self.messages.len() + message.len())
+            .map_err(|_| EigenDaError::validation("packed messages exceed 4 GiB"))?;
+        self.messages.extend_from_slice(message);
+        self.ends.push(end);
+        Ok(self.ends.len() as u32 - 1)
+    }
+
+    /// The packed blob, ready to disperse with
+    /// [`EigenDaGrpcClient::disperse_packed`](crate::client::EigenDaGrpcClient::disperse_packed).
+    pub fn pack(&self) -> Vec<u8> {
+        let mut packed = Vec::with_capacity(self.packed_len());
+        packed.extend((self.ends.len() as u32).to_be_bytes());
+        for end in &self.ends {
+            packed.extend(end.to_be_bytes());
+        }
+        packed.extend_from_slice(&self.messages);
+        packed
+    }
+
+    /// The locators of the packed messages, once the blob was confirmed at
+    /// `blob`.
+    pub fn locators(&self, blob: &BlobReference) -> Vec<MessageLocator> {
+        (0..self.ends.len() as u32)
+            .map(|index| MessageLocator::new(blob.clone(), index))
+            .collect()
+    }
+
+    /// Empty the packer, keeping its allocations.
+    pub fn clear(&mut self) {
+        self.ends.clear();
+        self.messages.clear();
+    }
+}
+
+fn read_u32(packed: &[u8], offset: usize) -> Result<u32> {
+    packed
+        .get(offset..offset + INDEX_ENTRY_LEN)
+        .map(|bytes| u32::from_be_bytes(bytes.try_into().expect("slice of 4 bytes.")))
+        .ok_or_else(|| EigenDaError::Codec("packed blob is shorter than its index".to_string()))
+}
+
+/// The number of messages in a packed blob.
+pub fn message_count(packed: &[u8]) -> Result<u32> {
+    read_u32(packed, 0)
+}
+
+/// The message at `index` of a packed blob, read through the index table
+/// without parsing the other messages.
+pub fn message(packed: &[u8], index: u32) -> Result<&[u8]> {
+    let count = message_count(packed)?;
+    if index >= count {
+        return Err(EigenDaError::Codec(format!(
+            "message index {} is out of range for a blob of {} messages",
+            index, count
+        )));
+    }
+    let messages_start = INDEX_ENTRY_LEN * (1 + count as usize);
+    let start = match index {
+        0 => 0,
+        _ => read_u32(packed, INDEX_ENTRY_LEN * index as usize)?,
+    } as usize;
+    let end = read_u32(packed, INDEX_ENTRY_LEN * (1 + index as usize))? as usize;
+    if start > end {
+        return Err(EigenDaError::Codec(format!(
+            "message {} ends before it starts",
+            index
+        )));
+    }
+    packed
+        .get(messages_start + start..messages_start + end)
+        .ok_or_else(|| {
+            EigenDaError::Codec(format!(
+                "message {} runs past the end of the packed blob",
+                index
+            ))
+        })
+}
@@ -1,30 +1,104 @@
+use crate::error::EigenDaError;
+use crate::result::Result;
+use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The largest quorum id the disperser accepts.
+pub const MAX_QUORUM_ID: u32 = 254;
+
+/// Optional fields of a dispersal request.
+#[derive(Builder, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[builder(default)]
+pub struct DisperseOptions {
+    /// Quorums to disperse to on top of the required ones.
+    custom_quorum_numbers: Vec<u32>,
+    /// Ignored by authenticated dispersal, which uses the signer's account.
+    #[builder(setter(into, strip_option))]
+    account_id: Option<String>,
+}
+
+impl DisperseOptions {
+    pub fn custom_quorum_numbers(&self) -> &[u32] {
+        &self.custom_quorum_numbers
+    }
+
+    pub fn account_id(&self) -> Option<&str> {
+        self.account_id.as_deref()
+    }
+
+    /// Check the options the way the disperser does, so a bad request
+    /// fails before it is sent.
+    pub fn validate(&self) -> Result<()> {
+        let mut seen = HashSet::new();
+        for quorum in &self.custom_quorum_numbers {
+            if *quorum > MAX_QUORUM_ID {
+                return Err(EigenDaError::validation(format!(
+                    "custom quorum number {} exceeds the maximum quorum id {}",
+                    quorum, MAX_QUORUM_ID
+                )));
+            }
+            if !seen.insert(quorum) {
+                return Err(EigenDaError::validation(format!(
+                    "custom quorum number {} is duplicated",
+                    quorum
+                )));
+            }
+        }
+        Ok(())
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EigenDaBlobPayload {
     data: String,
+    #[serde(flatten)]
+    options: DisperseOptions,
 }
 
 impl EigenDaBlobPayload {
     pub fn new(data: String) -> Self {
-        EigenDaBlobPayload { data }
+        EigenDaBlobPayload::with_options(data, DisperseOptions::default())
+    }
+
+    pub fn with_options(data: String, options: DisperseOptions) -> Self {
+        EigenDaBlobPayload { data, options }
+    }
+
+    /// Like [`EigenDaBlobPayload::with_options`], failing if the options
+    /// would be rejected by the disperser.
+    pub fn validated(data: String, options: DisperseOptions) -> Result<Self> {
+        options.validate()?;
+        Ok(EigenDaBlobPayload::with_options(data, options))
+    }
+
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    pub fn options(&self) -> &DisperseOptions {
+        &self.options
     }
 }
 
 impl From<EigenDaBlobPayload> for String {
     fn from(value: EigenDaBlobPayload) -> Self {
-        let payload = serde_json::json!({
-            "data": value.data
-        });
-        payload.to_string()
+        (&value).into()
     }
 }
 
 impl From<&EigenDaBlobPayload> for String {
     fn from(value: &EigenDaBlobPayload) -> Self {
-        let payload = serde_json::json!({
+        let mut payload = serde_json::json!({
             "data": value.data
         });
+        if !value.options.custom_quorum_numbers.is_empty() {
+            payload["custom_quorum_numbers"] =
+                serde_json::json!(value.options.custom_quorum_numbers);
+        }
+        if let Some(account_id) = &value.options.account_id {
+            payload["account_id"] = serde_json::json!(account_id);
+        }
         payload.to_string()
     }
 }
@@ -1,27 +1,33 @@
+use crate::batch::BlobBatchRoot;
+use crate::bytes;
+use crate::error::EigenDaError;
 use crate::meta::BatchMetadata;
 use crate::quorum::BlobQuorumIndexes;
+use crate::result::Result;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BlobVerificationProof {
-    batch_id: u128,
-    blob_index: Option<u128>,
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    batch_id: u32,
+    #[serde(default, deserialize_with = "crate::number::deserialize_option")]
+    blob_index: Option<u32>,
     batch_metadata: BatchMetadata,
     inclusion_proof: Option<BlobInclusionProof>,
     quorum_indexes: BlobQuorumIndexes,
 }
 
 impl BlobVerificationProof {
-    pub fn batch_id(&self) -> u128 {
+    pub fn batch_id(&self) -> u32 {
         self.batch_id
     }
 
-    pub fn blob_index(&self) -> u128 {
-        if let Some(index) = self.blob_index {
-            return index;
-        }
-        0
+    /// The index of the blob in its batch, `None` if the reply left it out.
+    pub fn blob_index(&self) -> Option<u32> {
+        self.blob_index
     }
 
     pub fn batch_metadata(&self) -> &BatchMetadata {
@@ -37,11 +43,162 @@ impl BlobVerificationProof {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct BlobInclusionProof(String);
+/// Bytes in each sibling hash of an inclusion proof.
+pub const SIBLING_HASH_LEN: usize = 32;
+
+/// The sibling hashes proving a blob header is in its batch root, from the
+/// leaf up. On the wire it is their concatenation, base64 encoded.
+#[derive(Clone, Default)]
+pub struct BlobInclusionProof(Vec<[u8; SIBLING_HASH_LEN]>);
+
+impl BlobInclusionProof {
+    pub fn new(siblings: Vec<[u8; SIBLING_HASH_LEN]>) -> Self {
+        BlobInclusionProof(siblings)
+    }
+
+    /// Split concatenated sibling hashes, rejecting a length that is not a
+    /// multiple of [`SIBLING_HASH_LEN`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if !bytes.len().is_multiple_of(SIBLING_HASH_LEN) {
+            return Err(EigenDaError::Codec(format!(
+                "inclusion proof of {} bytes is not a whole number of {} byte hashes",
+                bytes.len(),
+                SIBLING_HASH_LEN
+            )));
+        }
+        let siblings = bytes
+            .chunks_exact(SIBLING_HASH_LEN)
+            .map(bytes::to_array)
+            .collect::<Result<_>>()?;
+        Ok(BlobInclusionProof(siblings))
+    }
+
+    pub fn from_hex(value: &str) -> Result<Self> {
+        Self::from_bytes(&bytes::decode_hex(value)?)
+    }
+
+    pub fn from_base64(value: &str) -> Result<Self> {
+        Self::from_bytes(&base64::decode(value)?)
+    }
+
+    pub fn siblings(&self) -> &[[u8; SIBLING_HASH_LEN]] {
+        &self.0
+    }
+
+    /// The depth of the tree, one level per sibling hash.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The concatenated sibling hashes, the form verifier contracts take.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.concat()
+    }
+
+    pub fn to_hex(&self) -> String {
+        bytes::to_hex(&self.to_bytes())
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.to_bytes())
+    }
+
+    /// The root reached by hashing `leaf`, at `index` in the tree, with the
+    /// sibling hashes: `keccak256(left || right)` at each level.
+    pub fn root(&self, leaf: [u8; 32], index: u32) -> Result<[u8; 32]> {
+        let shifted = |level: usize| index.checked_shr(level as u32).unwrap_or(0);
+        if shifted(self.depth()) != 0 {
+            return Err(EigenDaError::Codec(format!(
+                "leaf index {} is out of range for a tree of depth {}",
+                index,
+                self.depth()
+            )));
+        }
+        let mut node = leaf;
+        for (level, sibling) in self.0.iter().enumerate() {
+            let mut hasher = Keccak256::new();
+            if shifted(level) & 1 == 1 {
+                hasher.update(sibling);
+                hasher.update(node);
+            } else {
+                hasher.update(node);
+                hasher.update(sibling);
+            }
+            node = hasher.finalize().into();
+        }
+        Ok(node)
+    }
+
+    /// Whether `leaf`, at `index`, is in the tree with root `root`.
+    pub fn verify(&self, leaf: [u8; 32], index: u32, root: &BlobBatchRoot) -> bool {
+        match self.root(leaf, index) {
+            Ok(computed) => BlobBatchRoot::new(computed) == *root,
+            Err(_) => false,
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for BlobInclusionProof {
+    type Error = EigenDaError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+/// Parses `0x` prefixed hex, or base64 otherwise, including base64 that
+/// starts with `0x`.
+impl std::str::FromStr for BlobInclusionProof {
+    type Err = EigenDaError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::from_bytes(&bytes::decode_hex_or_base64(value)?)
+    }
+}
+
+impl std::fmt::Display for BlobInclusionProof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl std::fmt::Debug for BlobInclusionProof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BlobInclusionProof")
+            .field(
+                &self
+                    .0
+                    .iter()
+                    .map(|hash| bytes::to_hex(hash))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl PartialEq for BlobInclusionProof {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes().ct_eq(&other.to_bytes()).into()
+    }
+}
+
+impl Eq for BlobInclusionProof {}
+
+impl Serialize for BlobInclusionProof {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        bytes::base64_serde::serialize(&self.to_bytes(), serializer)
+    }
+}
 
-impl ToString for BlobInclusionProof {
-    fn to_string(&self) -> String {
-        self.0.clone()
+/// Parses base64 only, as the disperser sends it.
+impl<'de> Deserialize<'de> for BlobInclusionProof {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let bytes = bytes::base64_serde::deserialize(deserializer)?;
+        Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
     }
 }
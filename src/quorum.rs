@@ -1,55 +1,117 @@
+use crate::error::EigenDaError;
+use crate::result::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct BlobQuorumIndexes(String);
 
-impl ToString for BlobQuorumIndexes {
-    fn to_string(&self) -> String {
-        self.0.clone()
+impl std::fmt::Display for BlobQuorumIndexes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BlobQuorumParams {
-    adversary_threshold_percentage: usize,
-    quorum_threshold_percentage: usize,
-    quantization_param: Option<usize>,
-    encoded_length: Option<String>,
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    quorum_number: u32,
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    adversary_threshold_percentage: u32,
+    #[serde(deserialize_with = "crate::number::deserialize")]
+    quorum_threshold_percentage: u32,
+    #[serde(default, deserialize_with = "crate::number::deserialize_option")]
+    quantization_param: Option<u32>,
+    #[serde(default, deserialize_with = "crate::number::deserialize_option")]
+    encoded_length: Option<u64>,
 }
 
 impl BlobQuorumParams {
-    pub fn adversary_threshold_percentage(&self) -> usize {
+    pub fn quorum_number(&self) -> u32 {
+        self.quorum_number
+    }
+
+    pub fn adversary_threshold_percentage(&self) -> u32 {
         self.adversary_threshold_percentage
     }
 
-    pub fn quorum_threshold_percentage(&self) -> usize {
+    pub fn quorum_threshold_percentage(&self) -> u32 {
         self.quorum_threshold_percentage
     }
 
-    pub fn quantization_param(&self) -> Option<usize> {
-        self.quantization_param.clone()
+    pub fn quantization_param(&self) -> Option<u32> {
+        self.quantization_param
     }
 
-    pub fn encoded_length(&self) -> Option<String> {
-        self.encoded_length.clone()
+    pub fn encoded_length(&self) -> Option<u64> {
+        self.encoded_length
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct BlobQuorumNumbers(String);
+/// The ids of the quorums a batch was signed by, one byte each.
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct BlobQuorumNumbers(#[serde(with = "crate::bytes::base64_serde")] Vec<u8>);
+
+impl BlobQuorumNumbers {
+    pub fn new(quorum_numbers: Vec<u8>) -> Self {
+        BlobQuorumNumbers(quorum_numbers)
+    }
 
-impl ToString for BlobQuorumNumbers {
-    fn to_string(&self) -> String {
-        self.0.clone()
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct BlobQuorumSignedPercentages(String);
+impl std::fmt::Display for BlobQuorumNumbers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+/// The percentage of stake that signed a batch in each of its quorums, in
+/// the order of its [`BlobQuorumNumbers`].
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct BlobQuorumSignedPercentages(#[serde(with = "crate::bytes::base64_serde")] Vec<u8>);
 
-impl ToString for BlobQuorumSignedPercentages {
-    fn to_string(&self) -> String {
-        self.0.clone()
+impl BlobQuorumSignedPercentages {
+    pub fn new(signed_percentages: Vec<u8>) -> Self {
+        BlobQuorumSignedPercentages(signed_percentages)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for BlobQuorumSignedPercentages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+/// Pair each quorum id with the percentage that signed in it, failing if
+/// the lists differ in length or a quorum is listed twice.
+pub fn signed_percentages_by_quorum(
+    quorum_numbers: &BlobQuorumNumbers,
+    signed_percentages: &BlobQuorumSignedPercentages,
+) -> Result<BTreeMap<u8, u8>> {
+    let (quorums, percentages) = (quorum_numbers.as_bytes(), signed_percentages.as_bytes());
+    if quorums.len() != percentages.len() {
+        return Err(EigenDaError::Codec(format!(
+            "batch lists {} quorums but {} signed percentages",
+            quorums.len(),
+            percentages.len()
+        )));
+    }
+    let mut by_quorum = BTreeMap::new();
+    for (quorum, percentage) in quorums.iter().zip(percentages) {
+        if by_quorum.insert(*quorum, *percentage).is_some() {
+            return Err(EigenDaError::Codec(format!(
+                "batch lists quorum {} twice",
+                quorum
+            )));
+        }
     }
+    Ok(by_quorum)
 }
@@ -1,10 +1,7 @@
-use serde::{Deserialize, Serialize};
+use crate::bytes::fixed_bytes;
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct BlobSignatoryRecordHash(String);
-
-impl ToString for BlobSignatoryRecordHash {
-    fn to_string(&self) -> String {
-        self.0.clone()
-    }
-}
+fixed_bytes!(
+    /// The keccak256 hash of the non-signers of a batch.
+    BlobSignatoryRecordHash,
+    32
+);
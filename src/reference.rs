@@ -0,0 +1,45 @@
+use crate::batch::BatchHeaderHash;
+use crate::error::EigenDaError;
+use crate::state::BlobState;
+use crate::status::BlobStatus;
+use serde::{Deserialize, Serialize};
+
+/// Where a confirmed blob can be retrieved from: its batch and its index in
+/// the batch.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlobReference {
+    batch_header_hash: BatchHeaderHash,
+    blob_index: u32,
+}
+
+impl BlobReference {
+    pub fn new(batch_header_hash: BatchHeaderHash, blob_index: u32) -> Self {
+        BlobReference {
+            batch_header_hash,
+            blob_index,
+        }
+    }
+
+    pub fn batch_header_hash(&self) -> &BatchHeaderHash {
+        &self.batch_header_hash
+    }
+
+    pub fn blob_index(&self) -> u32 {
+        self.blob_index
+    }
+}
+
+impl TryFrom<&BlobStatus> for BlobReference {
+    type Error = EigenDaError;
+
+    /// The reference of a confirmed or finalized blob.
+    fn try_from(status: &BlobStatus) -> Result<Self, Self::Error> {
+        match status.clone().into_state()? {
+            BlobState::Confirmed(blob) => Ok(blob.reference()),
+            _ => Err(EigenDaError::Dispersal(format!(
+                "blob is {}, not confirmed",
+                status.status()
+            ))),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Parsing of the disperser's JSON replies, from either transport.
+use crate::error::EigenDaError;
+use crate::result::Result;
+use serde::de::DeserializeOwned;
+
+/// Parse the JSON reply of a request into its model, skipping anything
+/// printed before it. Fails with [`EigenDaError::Json`], holding the whole
+/// reply, rather than returning an empty model.
+pub fn parse_reply<T: DeserializeOwned>(reply: String) -> Result<T> {
+    let json_str = &reply[reply.find('{').unwrap_or(0)..];
+    serde_json::from_str(json_str).map_err(|err| EigenDaError::json(err, reply.clone()))
+}
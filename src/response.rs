@@ -1,22 +1,16 @@
+use crate::error::EigenDaError;
+use crate::reply::parse_reply;
+use crate::result::Result;
 use crate::status::BlobResult;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct BlobResponse {
     result: BlobResult,
     #[serde(rename = "requestId")]
     request_id: String,
 }
 
-impl Default for BlobResponse {
-    fn default() -> Self {
-        BlobResponse {
-            result: Default::default(),
-            request_id: Default::default(),
-        }
-    }
-}
-
 impl BlobResponse {
     pub fn result(&self) -> &BlobResult {
         &self.result
@@ -27,23 +21,11 @@ impl BlobResponse {
     }
 }
 
-impl From<String> for BlobResponse {
-    fn from(value: String) -> Self {
-        if let Some(start_index) = value.find('{') {
-            let json_str = &value[start_index..];
-
-            let blob_response: Result<BlobResponse, serde_json::Error> =
-                serde_json::from_str(json_str);
+/// Parses the JSON reply of a dispersal with [`parse_reply`].
+impl TryFrom<String> for BlobResponse {
+    type Error = EigenDaError;
 
-            match blob_response {
-                Ok(response) => return response,
-                Err(err) => {
-                    log::error!("{}", &err);
-                    return BlobResponse::default();
-                }
-            }
-        } else {
-            return BlobResponse::default();
-        }
+    fn try_from(value: String) -> Result<Self> {
+        parse_reply(value)
     }
 }
@@ -1 +1,4 @@
+use crate::error::EigenDaError;
 
+/// The result of a request to the disperser.
+pub type Result<T, E = EigenDaError> = std::result::Result<T, E>;
@@ -0,0 +1,174 @@
+use crate::error::{EigenDaError, RETRYABLE_CODES};
+use crate::methods::EigenDaGrpcMethod;
+use crate::result::Result;
+use derive_builder::Builder;
+use rand::Rng;
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
+
+/// When and how often the clients resend a failed disperser request.
+///
+/// Only the methods in `methods` are retried. `DisperseBlob` is left out by
+/// default, as resending it after a reply was lost disperses the blob twice.
+/// gRPC statuses are retried when their code is in `retryable_codes`, other
+/// errors when [`EigenDaError::is_retryable`] says so.
+#[derive(Builder, Clone, Debug)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first one.
+    #[builder(default = "3")]
+    max_attempts: u32,
+    /// Delay before the first retry, doubled on every attempt after it.
+    #[builder(default = "Duration::from_millis(200)")]
+    initial_backoff: Duration,
+    #[builder(default = "Duration::from_secs(10)")]
+    max_backoff: Duration,
+    #[builder(default = "default_methods()", setter(custom))]
+    methods: HashSet<EigenDaGrpcMethod>,
+    #[builder(default = "default_retryable_codes()")]
+    retryable_codes: Vec<tonic::Code>,
+}
+
+fn default_methods() -> HashSet<EigenDaGrpcMethod> {
+    HashSet::from([
+        EigenDaGrpcMethod::GetBlobStatus,
+        EigenDaGrpcMethod::RetrieveBlob,
+    ])
+}
+
+fn default_retryable_codes() -> Vec<tonic::Code> {
+    RETRYABLE_CODES.to_vec()
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicyBuilder::default()
+            .build()
+            .expect("failed to build retry policy.")
+    }
+}
+
+impl RetryPolicyBuilder {
+    /// Turn retries of `method` on or off.
+    pub fn retry_method(&mut self, method: EigenDaGrpcMethod, enabled: bool) -> &mut Self {
+        let methods = self.methods.get_or_insert_with(default_methods);
+        if enabled {
+            methods.insert(method);
+        } else {
+            methods.remove(&method);
+        }
+        self
+    }
+}
+
+impl RetryPolicy {
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    pub fn retries_method(&self, method: EigenDaGrpcMethod) -> bool {
+        self.methods.contains(&method)
+    }
+
+    pub fn retryable_codes(&self) -> &[tonic::Code] {
+        &self.retryable_codes
+    }
+
+    /// Whether a request to `method` that failed with `err` on attempt
+    /// `attempt` (counting from 1) should be sent again.
+    pub fn should_retry(
+        &self,
+        method: EigenDaGrpcMethod,
+        attempt: u32,
+        err: &EigenDaError,
+    ) -> bool {
+        if attempt >= self.max_attempts || !self.retries_method(method) {
+            return false;
+        }
+        match err.grpc_status() {
+            Some(status) => self.retryable_codes.contains(&status.code()),
+            None => err.is_retryable(),
+        }
+    }
+
+    /// The delay after failed attempt `attempt`: exponential backoff capped
+    /// at `max_backoff`, with a random jitter of up to half the delay so
+    /// clients failing together do not retry together.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        let half = backoff / 2;
+        half + half.mul_f64(rand::thread_rng().gen::<f64>())
+    }
+
+    /// Run `request` until it succeeds or may not be retried, sleeping the
+    /// thread between attempts.
+    pub fn run<T, F>(&self, method: EigenDaGrpcMethod, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Result<T>,
+    {
+        let mut attempt = 1;
+        loop {
+            log::debug!("{} attempt {}/{}", method, attempt, self.max_attempts);
+            match request() {
+                Err(err) if self.should_retry(method, attempt, &err) => {
+                    let backoff = self.backoff(attempt);
+                    self.log_retry(method, attempt, &err, backoff);
+                    std::thread::sleep(backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// The async counterpart of [`RetryPolicy::run`], sleeping on the tokio
+    /// timer between attempts.
+    pub async fn run_async<T, F, Fut>(&self, method: EigenDaGrpcMethod, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            log::debug!("{} attempt {}/{}", method, attempt, self.max_attempts);
+            match request().await {
+                Err(err) if self.should_retry(method, attempt, &err) => {
+                    let backoff = self.backoff(attempt);
+                    self.log_retry(method, attempt, &err, backoff);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn log_retry(
+        &self,
+        method: EigenDaGrpcMethod,
+        attempt: u32,
+        err: &EigenDaError,
+        backoff: Duration,
+    ) {
+        log::warn!(
+            "{} attempt {}/{} failed: {}, retrying in {:?}",
+            method,
+            attempt,
+            self.max_attempts,
+            err,
+            backoff
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::error::EigenDaError;
+use crate::result::Result;
+use k256::ecdsa::SigningKey;
+use sha3::{Digest, Keccak256};
+use std::path::Path;
+
+/// Signs the nonce challenges of `DisperseBlobAuthenticated`, proving the
+/// dispersal comes from the account it claims.
+pub trait Signer: std::fmt::Debug + Send + Sync {
+    /// The account id sent with the dispersal request, the `0x` prefixed
+    /// hex of the uncompressed secp256k1 public key.
+    fn account_id(&self) -> String;
+
+    /// Sign the challenge parameter the disperser replied with, returning
+    /// the 65 byte `r || s || v` signature it expects.
+    fn sign_challenge(&self, challenge: u32) -> Result<Vec<u8>>;
+}
+
+/// The digest the disperser expects signed: the keccak256 hash of the
+/// big-endian challenge.
+pub fn challenge_digest(challenge: u32) -> [u8; 32] {
+    Keccak256::digest(challenge.to_be_bytes()).into()
+}
+
+/// A [`Signer`] holding an ECDSA secp256k1 private key in memory.
+#[derive(Clone)]
+pub struct LocalSigner {
+    key: SigningKey,
+}
+
+impl LocalSigner {
+    pub fn new(key: SigningKey) -> Self {
+        LocalSigner { key }
+    }
+
+    pub fn from_bytes(private_key: &[u8]) -> Result<Self> {
+        let key = SigningKey::from_slice(private_key).map_err(EigenDaError::signer)?;
+        Ok(LocalSigner::new(key))
+    }
+
+    /// Parse a hex private key, with or without a `0x` prefix.
+    pub fn from_hex(private_key: &str) -> Result<Self> {
+        let bytes =
+            hex::decode(private_key.trim_start_matches("0x")).map_err(EigenDaError::signer)?;
+        LocalSigner::from_bytes(&bytes)
+    }
+}
+
+impl std::fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("account_id", &self.account_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer for LocalSigner {
+    fn account_id(&self) -> String {
+        let public_key = self.key.verifying_key().to_encoded_point(false);
+        format!("0x{}", hex::encode(public_key.as_bytes()))
+    }
+
+    fn sign_challenge(&self, challenge: u32) -> Result<Vec<u8>> {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&challenge_digest(challenge))
+            .map_err(EigenDaError::signer)?;
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte());
+        Ok(bytes)
+    }
+}
+
+/// A [`Signer`] whose key is read from an encrypted Ethereum keystore file.
+#[derive(Clone, Debug)]
+pub struct KeystoreSigner {
+    signer: LocalSigner,
+}
+
+impl KeystoreSigner {
+    pub fn from_file<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let private_key =
+            eth_keystore::decrypt_key(path, password).map_err(EigenDaError::signer)?;
+        Ok(KeystoreSigner {
+            signer: LocalSigner::from_bytes(&private_key)?,
+        })
+    }
+}
+
+impl Signer for KeystoreSigner {
+    fn account_id(&self) -> String {
+        self.signer.account_id()
+    }
+
+    fn sign_challenge(&self, challenge: u32) -> Result<Vec<u8>> {
+        self.signer.sign_challenge(challenge)
+    }
+}
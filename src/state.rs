@@ -0,0 +1,245 @@
+//! The status of a blob as a state, where a confirmed blob always carries
+//! the information needed to retrieve and verify it.
+use crate::batch::{BatchHeader, BatchHeaderHash, BlobBatchRoot};
+use crate::commitment::BlobCommitment;
+use crate::error::EigenDaError;
+use crate::fee::BlobFee;
+use crate::header::BlobHeader;
+use crate::meta::BatchMetadata;
+use crate::proof::{BlobInclusionProof, BlobVerificationProof};
+use crate::quorum::{BlobQuorumIndexes, BlobQuorumParams};
+use crate::record::BlobSignatoryRecordHash;
+use crate::reference::BlobReference;
+use crate::result::Result;
+use crate::status::{BlobResult, BlobStatus};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Where a blob is in its dispersal, from [`BlobStatus::into_state`].
+#[derive(Clone, Debug)]
+pub enum BlobState {
+    /// The blob is being dispersed. Holds the status the disperser replied
+    /// with, `Processing` or `Dispersing`.
+    Pending(BlobResult),
+    /// The blob was confirmed on chain, and possibly finalized.
+    Confirmed(Box<ConfirmedBlob>),
+    /// The blob will never be confirmed. Holds the status it failed with.
+    Failed(BlobResult),
+    /// The disperser replied `UNKNOWN`, or with a status this crate does
+    /// not know. Polling may never leave this state, so callers should stop
+    /// rather than wait on it.
+    Unknown(BlobResult),
+}
+
+impl BlobState {
+    /// The confirmed blob, if the blob was confirmed.
+    pub fn confirmed(&self) -> Option<&ConfirmedBlob> {
+        match self {
+            BlobState::Confirmed(blob) => Some(blob),
+            _ => None,
+        }
+    }
+
+    pub fn into_confirmed(self) -> Option<ConfirmedBlob> {
+        match self {
+            BlobState::Confirmed(blob) => Some(*blob),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<BlobStatus> for BlobState {
+    type Error = EigenDaError;
+
+    /// Fails with [`EigenDaError::Dispersal`] if a confirmed status lacks
+    /// its blob header, verification proof or blob index.
+    fn try_from(status: BlobStatus) -> Result<Self> {
+        let (result, info) = status.into_parts();
+        if result.is_failure() {
+            return Ok(BlobState::Failed(result));
+        }
+        if result.is_pending() {
+            return Ok(BlobState::Pending(result));
+        }
+        if !result.is_success() {
+            return Ok(BlobState::Unknown(result));
+        }
+
+        let missing =
+            |what: &str| EigenDaError::Dispersal(format!("{} blob status has no {}", result, what));
+        let (header, proof) = info.ok_or_else(|| missing("blob info"))?.into_parts();
+        let header = header.ok_or_else(|| missing("blob header"))?;
+        let proof = proof.ok_or_else(|| missing("verification proof"))?;
+        let blob_index = proof.blob_index().ok_or_else(|| missing("blob index"))?;
+        // Proto3 leaves out empty bytes, so a missing inclusion proof is the
+        // empty proof of a blob alone in its batch.
+        let inclusion_proof = proof.inclusion_proof().clone().unwrap_or_default();
+        Ok(BlobState::Confirmed(Box::new(ConfirmedBlob {
+            status: result,
+            header,
+            proof,
+            blob_index,
+            inclusion_proof,
+        })))
+    }
+}
+
+/// The statuses polled while waiting for a blob to be confirmed, shared by
+/// both clients' `wait_for_confirmation`.
+pub(crate) struct ConfirmationWait<'a> {
+    request_id: &'a str,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl<'a> ConfirmationWait<'a> {
+    pub fn new(request_id: &'a str, timeout: Option<Duration>) -> Self {
+        ConfirmationWait {
+            request_id,
+            timeout,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+        }
+    }
+
+    /// The confirmed blob, or `None` if the blob is still pending and the
+    /// wait has time left. A failed or unknown status fails the wait rather
+    /// than being polled forever.
+    pub fn check(&self, status: BlobStatus) -> Result<Option<ConfirmedBlob>> {
+        match status.into_state()? {
+            BlobState::Confirmed(blob) => return Ok(Some(*blob)),
+            BlobState::Failed(result) => {
+                return Err(EigenDaError::Dispersal(format!(
+                    "blob {} failed with status {}",
+                    self.request_id, result
+                )))
+            }
+            BlobState::Unknown(result) => {
+                return Err(EigenDaError::Dispersal(format!(
+                    "blob {} has unexpected status {}",
+                    self.request_id, result
+                )))
+            }
+            BlobState::Pending(_) => {}
+        }
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.timeout) {
+            if Instant::now() >= deadline {
+                return Err(EigenDaError::Timeout(timeout));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A confirmed or finalized blob, with direct access to its header, its
+/// batch and the proofs of its inclusion.
+#[derive(Clone, Debug)]
+pub struct ConfirmedBlob {
+    status: BlobResult,
+    header: BlobHeader,
+    proof: BlobVerificationProof,
+    blob_index: u32,
+    inclusion_proof: BlobInclusionProof,
+}
+
+impl ConfirmedBlob {
+    /// `Confirmed` or `Finalized`.
+    pub fn status(&self) -> &BlobResult {
+        &self.status
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.status == BlobResult::Finalized
+    }
+
+    /// Where the blob can be retrieved from.
+    pub fn reference(&self) -> BlobReference {
+        BlobReference::new(self.batch_header_hash().clone(), self.blob_index)
+    }
+
+    pub fn blob_header(&self) -> &BlobHeader {
+        &self.header
+    }
+
+    pub fn commitment(&self) -> &BlobCommitment {
+        self.header.commitment()
+    }
+
+    pub fn data_length(&self) -> u32 {
+        self.header.data_length()
+    }
+
+    pub fn blob_quorum_params(&self) -> &[BlobQuorumParams] {
+        self.header.blob_quorum_params()
+    }
+
+    pub fn blob_verification_proof(&self) -> &BlobVerificationProof {
+        &self.proof
+    }
+
+    pub fn batch_id(&self) -> u32 {
+        self.proof.batch_id()
+    }
+
+    pub fn blob_index(&self) -> u32 {
+        self.blob_index
+    }
+
+    pub fn inclusion_proof(&self) -> &BlobInclusionProof {
+        &self.inclusion_proof
+    }
+
+    pub fn quorum_indexes(&self) -> &BlobQuorumIndexes {
+        self.proof.quorum_indexes()
+    }
+
+    pub fn batch_metadata(&self) -> &BatchMetadata {
+        self.proof.batch_metadata()
+    }
+
+    pub fn batch_header(&self) -> &BatchHeader {
+        self.batch_metadata().batch_header()
+    }
+
+    pub fn batch_header_hash(&self) -> &BatchHeaderHash {
+        self.batch_metadata().batch_header_hash()
+    }
+
+    pub fn batch_root(&self) -> &BlobBatchRoot {
+        self.batch_header().batch_root()
+    }
+
+    pub fn signatory_record_hash(&self) -> &BlobSignatoryRecordHash {
+        self.batch_metadata().signatory_record_hash()
+    }
+
+    pub fn fee(&self) -> &BlobFee {
+        self.batch_metadata().fee()
+    }
+
+    pub fn confirmation_block_number(&self) -> u32 {
+        self.batch_metadata().confirmation_block_number()
+    }
+
+    pub fn reference_block_number(&self) -> u32 {
+        self.batch_header().reference_block_number()
+    }
+
+    /// The percentage of stake that signed in each quorum of the blob's
+    /// batch, by quorum id.
+    pub fn signed_percentages(&self) -> Result<BTreeMap<u8, u8>> {
+        self.batch_header().signed_percentages()
+    }
+
+    /// The percentage of stake that signed in `quorum_id`, if the blob's
+    /// batch was signed by it.
+    pub fn signed_percentage(&self, quorum_id: u8) -> Result<Option<u8>> {
+        self.batch_header().signed_percentage(quorum_id)
+    }
+
+    /// Check the inclusion proof of the blob's leaf hash `leaf` against the
+    /// batch root.
+    pub fn verify_inclusion(&self, leaf: [u8; 32]) -> bool {
+        self.inclusion_proof
+            .verify(leaf, self.blob_index, self.batch_root())
+    }
+}
@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use crate::batch::{BatchHeader, BatchHeaderHash, BlobBatchRoot};
 use crate::commitment::BlobCommitment;
+use crate::error::EigenDaError;
 use crate::fee::BlobFee;
 use crate::header::BlobHeader;
 use crate::info::BlobInfo;
@@ -11,26 +12,151 @@ use crate::quorum::{
     BlobQuorumIndexes, BlobQuorumNumbers, BlobQuorumParams, BlobQuorumSignedPercentages,
 };
 use crate::record::BlobSignatoryRecordHash;
+use crate::reply::parse_reply;
+use crate::result::Result;
+use crate::state::BlobState;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-// TODO: Implement custom Deserialize
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
-#[serde(rename_all = "UPPERCASE")]
+/// The status of a dispersed blob, as the `disperser.BlobStatus` proto enum.
+/// Statuses this crate does not know yet are kept as `Other`.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum BlobResult {
+    #[default]
+    Unknown,
     Processing,
-    Finalized,
     Confirmed,
     Failed,
+    Finalized,
+    InsufficientSignatures,
+    Dispersing,
     Other(String),
 }
 
-impl Default for BlobResult {
-    fn default() -> Self {
-        BlobResult::Other("Default".to_string())
+impl BlobResult {
+    /// The proto enum name of the status.
+    pub fn as_str(&self) -> &str {
+        match self {
+            BlobResult::Unknown => "UNKNOWN",
+            BlobResult::Processing => "PROCESSING",
+            BlobResult::Confirmed => "CONFIRMED",
+            BlobResult::Failed => "FAILED",
+            BlobResult::Finalized => "FINALIZED",
+            BlobResult::InsufficientSignatures => "INSUFFICIENT_SIGNATURES",
+            BlobResult::Dispersing => "DISPERSING",
+            BlobResult::Other(name) => name,
+        }
+    }
+
+    /// The blob is still being dispersed and its status will change.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, BlobResult::Processing | BlobResult::Dispersing)
+    }
+
+    /// The blob was confirmed on chain, and possibly finalized.
+    pub fn is_success(&self) -> bool {
+        matches!(self, BlobResult::Confirmed | BlobResult::Finalized)
+    }
+
+    /// The blob will never be confirmed.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            BlobResult::Failed | BlobResult::InsufficientSignatures
+        )
+    }
+
+    /// Polling for confirmation can stop: the blob either succeeded or
+    /// failed. Unknown statuses are not terminal.
+    pub fn is_terminal(&self) -> bool {
+        self.is_success() || self.is_failure()
+    }
+}
+
+impl From<&str> for BlobResult {
+    fn from(value: &str) -> Self {
+        match value {
+            "UNKNOWN" => BlobResult::Unknown,
+            "PROCESSING" => BlobResult::Processing,
+            "CONFIRMED" => BlobResult::Confirmed,
+            "FAILED" => BlobResult::Failed,
+            "FINALIZED" => BlobResult::Finalized,
+            "INSUFFICIENT_SIGNATURES" => BlobResult::InsufficientSignatures,
+            "DISPERSING" => BlobResult::Dispersing,
+            other => BlobResult::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for BlobResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for BlobResult {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Accepts the proto enum name, as grpcurl prints it, or its number, which
+/// proto3 JSON also allows.
+impl<'de> Deserialize<'de> for BlobResult {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        struct BlobResultVisitor;
+
+        impl serde::de::Visitor<'_> for BlobResultVisitor {
+            type Value = BlobResult;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a blob status name or number")
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                value: &str,
+            ) -> std::result::Result<BlobResult, E> {
+                Ok(BlobResult::from(value))
+            }
+
+            fn visit_u64<E: serde::de::Error>(
+                self,
+                value: u64,
+            ) -> std::result::Result<BlobResult, E> {
+                Ok(match value {
+                    0 => BlobResult::Unknown,
+                    1 => BlobResult::Processing,
+                    2 => BlobResult::Confirmed,
+                    3 => BlobResult::Failed,
+                    4 => BlobResult::Finalized,
+                    5 => BlobResult::InsufficientSignatures,
+                    6 => BlobResult::Dispersing,
+                    other => BlobResult::Other(other.to_string()),
+                })
+            }
+
+            fn visit_i64<E: serde::de::Error>(
+                self,
+                value: i64,
+            ) -> std::result::Result<BlobResult, E> {
+                match u64::try_from(value) {
+                    Ok(value) => self.visit_u64(value),
+                    Err(_) => Ok(BlobResult::Other(value.to_string())),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(BlobResultVisitor)
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct BlobStatus {
     status: BlobResult,
     info: Option<BlobInfo>,
@@ -45,6 +171,17 @@ impl BlobStatus {
         &self.info
     }
 
+    pub fn into_parts(self) -> (BlobResult, Option<BlobInfo>) {
+        (self.status, self.info)
+    }
+
+    /// The status as a [`BlobState`], whose confirmed variant gives direct
+    /// access to the blob's header, batch and proofs. Fails if a confirmed
+    /// status is missing any of them.
+    pub fn into_state(self) -> Result<BlobState> {
+        BlobState::try_from(self)
+    }
+
     pub fn blob_header(&self) -> Option<&BlobHeader> {
         if let Some(info) = &self.info() {
             return info.blob_header();
@@ -68,7 +205,7 @@ impl BlobStatus {
         None
     }
 
-    pub fn data_length(&self) -> Option<usize> {
+    pub fn data_length(&self) -> Option<u32> {
         if let Some(header) = &self.blob_header() {
             return Some(header.data_length());
         }
@@ -84,18 +221,16 @@ impl BlobStatus {
         None
     }
 
-    pub fn batch_id(&self) -> Option<u128> {
+    pub fn batch_id(&self) -> Option<u32> {
         if let Some(proof) = self.blob_verification_proof() {
             return Some(proof.batch_id());
         }
         None
     }
 
-    pub fn blob_index(&self) -> Option<u128> {
-        if let Some(proof) = self.blob_verification_proof() {
-            return Some(proof.blob_index());
-        }
-        None
+    pub fn blob_index(&self) -> Option<u32> {
+        self.blob_verification_proof()
+            .and_then(BlobVerificationProof::blob_index)
     }
 
     pub fn batch_metadata(&self) -> Option<&BatchMetadata> {
@@ -143,7 +278,7 @@ impl BlobStatus {
         None
     }
 
-    pub fn confirmation_block_number(&self) -> Option<u128> {
+    pub fn confirmation_block_number(&self) -> Option<u32> {
         if let Some(metadata) = self.batch_metadata() {
             return Some(metadata.confirmation_block_number());
         }
@@ -182,7 +317,24 @@ impl BlobStatus {
         None
     }
 
-    pub fn reference_block_number(&self) -> Option<u128> {
+    /// The percentage of stake that signed in each quorum of the blob's
+    /// batch, by quorum id.
+    pub fn signed_percentages(&self) -> Result<Option<BTreeMap<u8, u8>>> {
+        self.batch_header()
+            .map(BatchHeader::signed_percentages)
+            .transpose()
+    }
+
+    /// The percentage of stake that signed in `quorum_id`, if the blob's
+    /// batch was signed by it.
+    pub fn signed_percentage(&self, quorum_id: u8) -> Result<Option<u8>> {
+        match self.batch_header() {
+            Some(header) => header.signed_percentage(quorum_id),
+            None => Ok(None),
+        }
+    }
+
+    pub fn reference_block_number(&self) -> Option<u32> {
         if let Some(header) = self.batch_header() {
             return Some(header.reference_block_number());
         }
@@ -190,18 +342,12 @@ impl BlobStatus {
     }
 }
 
-impl From<String> for BlobStatus {
-    fn from(value: String) -> Self {
-        if let Some(start_index) = value.find('{') {
-            let json_str = &value[start_index..];
-            let blob_status: Result<BlobStatus, serde_json::Error> = serde_json::from_str(json_str);
-            match blob_status {
-                Ok(status) => return status,
-                Err(_) => return BlobStatus::default(),
-            }
-        } else {
-            return BlobStatus::default();
-        }
+/// Parses the JSON reply of a status request with [`parse_reply`].
+impl TryFrom<String> for BlobStatus {
+    type Error = EigenDaError;
+
+    fn try_from(value: String) -> Result<Self> {
+        parse_reply(value)
     }
 }
 
@@ -211,12 +357,3 @@ impl FromStr for BlobStatus {
         serde_json::from_str(s)
     }
 }
-
-impl Default for BlobStatus {
-    fn default() -> Self {
-        BlobStatus {
-            status: Default::default(),
-            info: Default::default(),
-        }
-    }
-}
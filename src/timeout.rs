@@ -0,0 +1,61 @@
+use crate::error::EigenDaError;
+use crate::result::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// How long a disperser request may take. Unset timeouts leave the request
+/// unbounded, or bounded by the transport's own default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    connect: Option<Duration>,
+    rpc: Option<Duration>,
+}
+
+impl Timeouts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time allowed to connect to the disperser.
+    pub fn with_connect(mut self, timeout: Duration) -> Self {
+        self.connect = Some(timeout);
+        self
+    }
+
+    /// Time allowed for a single RPC, connecting included. When a retry
+    /// policy is set, every attempt gets the full deadline.
+    pub fn with_rpc(mut self, timeout: Duration) -> Self {
+        self.rpc = Some(timeout);
+        self
+    }
+
+    pub fn connect(&self) -> Option<Duration> {
+        self.connect
+    }
+
+    pub fn rpc(&self) -> Option<Duration> {
+        self.rpc
+    }
+
+    /// These timeouts, with the ones set in `overrides` taking precedence.
+    pub fn merge(&self, overrides: &Timeouts) -> Timeouts {
+        Timeouts {
+            connect: overrides.connect.or(self.connect),
+            rpc: overrides.rpc.or(self.rpc),
+        }
+    }
+}
+
+/// Run `future` to completion, or drop it and fail with
+/// [`EigenDaError::Timeout`] once `timeout` passes.
+pub(crate) async fn with_timeout<T, F>(timeout: Option<Duration>, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| EigenDaError::Timeout(timeout))?,
+        None => future.await,
+    }
+}
@@ -0,0 +1,108 @@
+use crate::batch::BatchHeaderHash;
+use crate::blob::EncodedBlob;
+use crate::error::EigenDaError;
+use crate::payload::EigenDaBlobPayload;
+use crate::response::BlobResponse;
+use crate::result::Result;
+use crate::signer::Signer;
+use crate::status::BlobStatus;
+use crate::timeout::Timeouts;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// The wire used by [`EigenDaGrpcClient`](crate::client::EigenDaGrpcClient)
+/// to reach the disperser, one method per disperser RPC.
+///
+/// The crate ships [`TonicTransport`](crate::native::TonicTransport) and,
+/// with the `grpcurl` feature, `GrpcurlTransport`. Implement it to plug in
+/// another backend, or a fake for tests.
+///
+/// Every request is bounded by `timeouts`. Implementations should cancel the
+/// request once a timeout passes and fail with
+/// [`EigenDaError::Timeout`](crate::error::EigenDaError::Timeout).
+pub trait DisperserTransport: std::fmt::Debug + Send + Sync {
+    /// `disperser.Disperser/DisperseBlob`
+    fn disperse_blob(
+        &self,
+        payload: &EigenDaBlobPayload,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse>;
+
+    /// `disperser.Disperser/DisperseBlobAuthenticated`, answering the
+    /// disperser's nonce challenge with `signer`.
+    fn disperse_blob_authenticated(
+        &self,
+        _payload: &EigenDaBlobPayload,
+        _signer: &dyn Signer,
+        _timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        Err(unsupported_authenticated_dispersal())
+    }
+
+    /// `disperser.Disperser/GetBlobStatus`
+    fn get_blob_status(&self, request_id: &str, timeouts: &Timeouts) -> Result<BlobStatus>;
+
+    /// `disperser.Disperser/RetrieveBlob`
+    fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        timeouts: &Timeouts,
+    ) -> Result<EncodedBlob>;
+
+    /// A copy of the transport that sends to the disperser at `address`,
+    /// used by
+    /// [`EigenDaGrpcClient::set_server_address`](crate::client::EigenDaGrpcClient::set_server_address).
+    fn with_server_address(&self, _address: &str) -> Result<Arc<dyn DisperserTransport>> {
+        Err(EigenDaError::validation(
+            "changing the server address is not supported by this transport",
+        ))
+    }
+
+    /// A copy of the transport that runs the `grpcurl` binary at `path`,
+    /// used by the deprecated `EigenDaGrpcClient::update_grpcurl_bin_path`.
+    #[cfg(feature = "grpcurl")]
+    fn with_grpcurl_bin_path(&self, _path: Option<String>) -> Result<Arc<dyn DisperserTransport>> {
+        Err(EigenDaError::validation(
+            "this transport does not run grpcurl",
+        ))
+    }
+}
+
+/// The non-blocking counterpart of [`DisperserTransport`], used by
+/// [`AsyncEigenDaClient`](crate::async_client::AsyncEigenDaClient).
+#[async_trait]
+pub trait AsyncDisperserTransport: std::fmt::Debug + Send + Sync {
+    /// `disperser.Disperser/DisperseBlob`
+    async fn disperse_blob(
+        &self,
+        payload: &EigenDaBlobPayload,
+        timeouts: &Timeouts,
+    ) -> Result<BlobResponse>;
+
+    /// `disperser.Disperser/DisperseBlobAuthenticated`, answering the
+    /// disperser's nonce challenge with `signer`.
+    async fn disperse_blob_authenticated(
+        &self,
+        _payload: &EigenDaBlobPayload,
+        _signer: &dyn Signer,
+        _timeouts: &Timeouts,
+    ) -> Result<BlobResponse> {
+        Err(unsupported_authenticated_dispersal())
+    }
+
+    /// `disperser.Disperser/GetBlobStatus`
+    async fn get_blob_status(&self, request_id: &str, timeouts: &Timeouts) -> Result<BlobStatus>;
+
+    /// `disperser.Disperser/RetrieveBlob`
+    async fn retrieve_blob(
+        &self,
+        batch_header_hash: &BatchHeaderHash,
+        blob_index: u32,
+        timeouts: &Timeouts,
+    ) -> Result<EncodedBlob>;
+}
+
+fn unsupported_authenticated_dispersal() -> EigenDaError {
+    EigenDaError::validation("authenticated dispersal is not supported by this transport")
+}
@@ -0,0 +1,110 @@
+//! Serialization of typed records into dispersed data.
+//!
+//! A typed record is stored as its type tag, length-prefixed, followed by
+//! the record serialized in a [`Format`]. The format is recorded in the
+//! [`envelope`](crate::envelope) header, the type tag lets readers check
+//! they are decoding the type that was dispersed.
+use crate::error::EigenDaError;
+use crate::result::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// The longest type tag a record can carry.
+pub const MAX_TYPE_TAG_LEN: usize = u8::MAX as usize;
+
+/// How dispersed data is serialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Format {
+    /// Plain bytes, with no type tag.
+    #[default]
+    Raw = 0,
+    Json = 1,
+    /// The compact binary encoding of `bincode`.
+    Bincode = 2,
+    /// Messages packed by a [`BlobPacker`](crate::packer::BlobPacker),
+    /// with no type tag.
+    Packed = 3,
+}
+
+impl Format {
+    /// Whether data in this format is a typed record, starting with a type
+    /// tag.
+    pub fn is_typed(&self) -> bool {
+        matches!(self, Format::Json | Format::Bincode)
+    }
+}
+
+impl TryFrom<u8> for Format {
+    type Error = EigenDaError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Format::Raw),
+            1 => Ok(Format::Json),
+            2 => Ok(Format::Bincode),
+            3 => Ok(Format::Packed),
+            _ => Err(EigenDaError::Codec(format!("unknown format {}", value))),
+        }
+    }
+}
+
+/// Serialize `value` in `format`, prefixed with `type_tag`.
+pub fn serialize<T: Serialize>(value: &T, format: Format, type_tag: &str) -> Result<Vec<u8>> {
+    if type_tag.len() > MAX_TYPE_TAG_LEN {
+        return Err(EigenDaError::validation(format!(
+            "type tag of {} bytes exceeds the maximum of {}",
+            type_tag.len(),
+            MAX_TYPE_TAG_LEN
+        )));
+    }
+    let body = match format {
+        Format::Raw | Format::Packed => {
+            return Err(EigenDaError::validation(format!(
+                "typed records cannot be serialized as {:?}",
+                format
+            )))
+        }
+        Format::Json => serde_json::to_vec(value).map_err(|err| EigenDaError::json(err, ""))?,
+        Format::Bincode => {
+            bincode::serialize(value).map_err(|err| EigenDaError::Codec(err.to_string()))?
+        }
+    };
+
+    let mut record = Vec::with_capacity(1 + type_tag.len() + body.len());
+    record.push(type_tag.len() as u8);
+    record.extend_from_slice(type_tag.as_bytes());
+    record.extend(body);
+    Ok(record)
+}
+
+/// Split a record written by [`serialize`] into its type tag and body.
+pub fn split_type_tag(record: &[u8]) -> Result<(String, &[u8])> {
+    let (len, rest) = record
+        .split_first()
+        .ok_or_else(|| EigenDaError::Codec("typed record is empty".to_string()))?;
+    let len = *len as usize;
+    if rest.len() < len {
+        return Err(EigenDaError::Codec(
+            "typed record is shorter than its type tag".to_string(),
+        ));
+    }
+    let type_tag = String::from_utf8(rest[..len].to_vec())
+        .map_err(|err| EigenDaError::Codec(format!("type tag is not UTF-8: {}", err)))?;
+    Ok((type_tag, &rest[len..]))
+}
+
+/// Deserialize a record body written in `format`.
+pub fn deserialize<T: DeserializeOwned>(body: &[u8], format: Format) -> Result<T> {
+    match format {
+        Format::Raw | Format::Packed => Err(EigenDaError::Codec(format!(
+            "{:?} data cannot be deserialized into a typed record",
+            format
+        ))),
+        Format::Json => serde_json::from_slice(body)
+            .map_err(|err| EigenDaError::json(err, String::from_utf8_lossy(body))),
+        Format::Bincode => {
+            bincode::deserialize(body).map_err(|err| EigenDaError::Codec(err.to_string()))
+        }
+    }
+}